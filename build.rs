@@ -0,0 +1,71 @@
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::path::Path;
+
+const FIXTURES_DIR: &str = "fixtures";
+const MANIFEST: &str = "fixtures/examples.toml";
+
+/// Whether only the encrypted version of the input is there, as `encryption::needs_key` tells.
+fn needs_key(path: &Path) -> bool {
+    let mut encrypted_path = path.as_os_str().to_owned();
+    encrypted_path.push(".enc");
+    !path.exists() && Path::new(&encrypted_path).exists()
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", MANIFEST);
+
+    let contents = std::fs::read_to_string(MANIFEST).expect("fixtures manifest should exist");
+    let manifest: toml::Table = contents.parse().expect("fixtures manifest should be toml");
+    let examples = manifest
+        .get("example")
+        .and_then(|examples| examples.as_array())
+        .map_or(&[][..], |examples| examples.as_slice());
+
+    let mut tests = String::new();
+    let mut test_names = HashSet::new();
+    for example in examples {
+        let year = example.get("year").map_or(2023, |year| {
+            year.as_integer().expect("year should be a number")
+        });
+        let day = example["day"].as_integer().expect("day should be a number");
+        let name = example["name"].as_str().expect("name should be a string");
+        // tests of inputs only committed encrypted are ignored rather than passing without a key
+        let ignore = match example.get("path") {
+            Some(path) => {
+                let path =
+                    Path::new(FIXTURES_DIR).join(path.as_str().expect("path should be text"));
+                if let Some(directory) = path.parent() {
+                    println!("cargo:rerun-if-changed={}", directory.display());
+                }
+                needs_key(&path)
+            }
+            None => false,
+        };
+        for part in [1, 2] {
+            if example.get(format!("part{}", part)).is_none() {
+                continue;
+            }
+            let identifier = name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect::<String>();
+            let test_name = format!("y{}_day{}_{}_part{}", year, day, identifier, part);
+            if !test_names.insert(test_name.clone()) {
+                panic!("two examples of day {} of {} are named {}", day, year, name);
+            }
+            if ignore {
+                writeln!(tests, "#[ignore = \"needs input key\"]").unwrap();
+            }
+            writeln!(
+                tests,
+                "#[test]\nfn {}() {{\n    check_example({}, {}, {:?}, {});\n}}\n",
+                test_name, year, day, name, part
+            )
+            .unwrap();
+        }
+    }
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    std::fs::write(Path::new(&out_dir).join("fixture_tests.rs"), tests).unwrap();
+}
@@ -0,0 +1,44 @@
+use std::time::{Duration, Instant};
+
+pub fn time_function<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let now = Instant::now();
+    (f(), now.elapsed())
+}
+
+pub fn choose_unit(duration: Duration) -> (u128, String) {
+    if duration < Duration::from_millis(10) {
+        (duration.as_micros(), "µs".to_string())
+    } else if duration < Duration::from_secs(10) {
+        (duration.as_millis(), "ms".to_string())
+    } else {
+        (duration.as_secs() as u128, "s".to_string())
+    }
+}
+
+pub fn get_option<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
+
+// options taking no value
+const FLAGS: [&str; 5] = ["--explain", "--strict", "--bigint", "--lines", "--report"];
+
+pub fn get_params(args: &[String]) -> Vec<(&str, &str)> {
+    // `name=value` arguments, leaving out the values of `--option value` pairs
+    let mut params = vec![];
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "--bigint" {
+            params.push(("bigint", "true"));
+        } else if arg.starts_with("--") {
+            if !arg.contains('=') && !FLAGS.contains(&arg.as_str()) {
+                args.next();
+            }
+        } else if let Some(param) = arg.split_once('=') {
+            params.push(param);
+        }
+    }
+    params
+}
@@ -0,0 +1,468 @@
+use regex::Regex;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::encryption;
+
+const BASE_URL_VAR: &str = "AOC_BASE_URL";
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_VAR: &str = "AOC_SESSION";
+const SESSION_FILE_VAR: &str = "AOC_SESSION_FILE";
+const DEFAULT_SESSION_FILE: &str = "./.session";
+const USER_AGENT: &str = concat!("aoc/", env!("CARGO_PKG_VERSION"));
+/// The time to leave between two requests to the site, even across runs.
+const MIN_INTERVAL: Duration = Duration::from_secs(5);
+const HISTORY_FILE: &str = "submissions.txt";
+
+/// What the site says of a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// Too early to submit again, for that long.
+    Wait(Duration),
+    /// The part is already solved, or not unlocked yet.
+    WrongLevel,
+}
+
+impl Verdict {
+    fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait(_) => "wait",
+            Verdict::WrongLevel => "wrong_level",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Verdict> {
+        match name {
+            "correct" => Some(Verdict::Correct),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "too high"),
+            Verdict::TooLow => write!(f, "too low"),
+            Verdict::Wrong => write!(f, "wrong"),
+            Verdict::Wait(duration) => write!(f, "too soon, wait {}s", duration.as_secs()),
+            Verdict::WrongLevel => write!(f, "part already solved or still locked"),
+        }
+    }
+}
+
+/// Reads the verdict in the page answering a submission.
+pub fn parse_response(page: &str) -> Result<Verdict, String> {
+    if page.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if page.contains("You gave an answer too recently") {
+        let left = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let wait = left.captures(page).map_or(Duration::ZERO, |captures| {
+            let minutes = captures.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+            let seconds: u64 = captures[2].parse().unwrap();
+            Duration::from_secs(60 * minutes + seconds)
+        });
+        Ok(Verdict::Wait(wait))
+    } else if page.contains("That's not the right answer") {
+        if page.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else {
+            Ok(Verdict::Wrong)
+        }
+    } else if page.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::WrongLevel)
+    } else {
+        Err("unexpected answer from the site".to_string())
+    }
+}
+
+fn unescape(html: &str) -> String {
+    let tag = Regex::new(r"<[^>]*>").unwrap();
+    tag.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Extracts the multi-line code blocks of a puzzle page, which hold the example inputs.
+pub fn extract_examples(page: &str) -> Vec<String> {
+    let block = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    block
+        .captures_iter(page)
+        .map(|captures| unescape(&captures[1]))
+        .filter(|example| example.contains('\n'))
+        .collect()
+}
+
+/// The answers submitted for a day and what the site said of them, kept next to its input.
+#[derive(Debug, Default, PartialEq)]
+pub struct History {
+    submissions: Vec<(u8, String, Verdict)>,
+}
+
+impl History {
+    pub fn path(input_dir: &Path) -> PathBuf {
+        input_dir.join(HISTORY_FILE)
+    }
+
+    pub fn load(path: &Path) -> Result<History, String> {
+        let Ok(contents) = std::fs::read_to_string(path) else {
+            return Ok(History::default());
+        };
+        let submissions = contents
+            .lines()
+            .map(|line| {
+                let mut fields = line.splitn(3, ' ');
+                let part = fields.next().and_then(|part| part.parse().ok());
+                let verdict = fields.next().and_then(Verdict::from_name);
+                match (part, verdict, fields.next()) {
+                    (Some(part), Some(verdict), Some(answer)) => {
+                        Ok((part, answer.to_string(), verdict))
+                    }
+                    _ => Err(format!("invalid line in {}: {}", path.display(), line)),
+                }
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(History { submissions })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let contents = self
+            .submissions
+            .iter()
+            .map(|(part, answer, verdict)| format!("{} {} {}\n", part, verdict.name(), answer))
+            .collect::<String>();
+        std::fs::write(path, contents)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))
+    }
+
+    /// Refuses an answer already submitted, or outside the bounds given by the previous ones.
+    pub fn check(&self, part: u8, answer: &str) -> Result<(), String> {
+        let number = answer.parse::<i128>().ok();
+        for (_, submitted, verdict) in self.submissions.iter().filter(|s| s.0 == part) {
+            let bound = submitted.parse::<i128>().ok();
+            match (verdict, number, bound) {
+                (Verdict::Correct, _, _) => {
+                    return Err(format!("part {} is already solved: {}", part, submitted))
+                }
+                _ if submitted == answer => {
+                    return Err(format!("{} was already submitted: {}", answer, verdict))
+                }
+                (Verdict::TooHigh, Some(number), Some(bound)) if number >= bound => {
+                    return Err(format!("{} is too high, {} already was", answer, submitted))
+                }
+                (Verdict::TooLow, Some(number), Some(bound)) if number <= bound => {
+                    return Err(format!("{} is too low, {} already was", answer, submitted))
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, part: u8, answer: &str, verdict: &Verdict) {
+        if Verdict::from_name(verdict.name()).is_some() {
+            self.submissions
+                .push((part, answer.to_string(), verdict.clone()));
+        }
+    }
+}
+
+pub struct Config {
+    pub base_url: String,
+    pub session: String,
+    pub min_interval: Duration,
+    /// Where the time of the last request is kept, to space requests out across runs.
+    pub stamp_path: PathBuf,
+}
+
+impl Config {
+    /// Reads the base URL from `AOC_BASE_URL`, and the session cookie from `AOC_SESSION` or the
+    /// session file.
+    pub fn from_env(stamp_path: PathBuf) -> Result<Config, String> {
+        let session = match std::env::var(SESSION_VAR) {
+            Ok(session) => session,
+            Err(_) => {
+                let path =
+                    std::env::var(SESSION_FILE_VAR).unwrap_or(DEFAULT_SESSION_FILE.to_string());
+                std::fs::read_to_string(&path).map_err(|_| {
+                    format!(
+                        "no session cookie, set {} or write it to {}",
+                        SESSION_VAR, path
+                    )
+                })?
+            }
+        };
+        Ok(Config {
+            base_url: std::env::var(BASE_URL_VAR).unwrap_or(DEFAULT_BASE_URL.to_string()),
+            session: session.trim().to_string(),
+            min_interval: MIN_INTERVAL,
+            stamp_path,
+        })
+    }
+}
+
+pub struct Client {
+    agent: ureq::Agent,
+    config: Config,
+}
+
+impl Client {
+    pub fn new(config: Config) -> Client {
+        let agent = ureq::AgentBuilder::new()
+            .timeout(Duration::from_secs(30))
+            .user_agent(USER_AGENT)
+            .build();
+        Client { agent, config }
+    }
+
+    fn url(&self, year: u32, day: u32, page: &str) -> String {
+        format!(
+            "{}/{}/day/{}{}",
+            self.config.base_url.trim_end_matches('/'),
+            year,
+            day,
+            page
+        )
+    }
+
+    /// Sleeps until `min_interval` after the last request, then marks the time of this one.
+    fn wait_turn(&self) -> Result<(), String> {
+        let now = || SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let last = std::fs::read_to_string(&self.config.stamp_path)
+            .ok()
+            .and_then(|stamp| stamp.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(last) = last {
+            let next = last + self.config.min_interval;
+            if let Some(wait) = next.checked_sub(now()) {
+                std::thread::sleep(wait);
+            }
+        }
+        if let Some(directory) = self.config.stamp_path.parent() {
+            std::fs::create_dir_all(directory)
+                .map_err(|e| format!("cannot create {}: {}", directory.display(), e))?;
+        }
+        std::fs::write(&self.config.stamp_path, now().as_millis().to_string())
+            .map_err(|e| format!("cannot write {}: {}", self.config.stamp_path.display(), e))
+    }
+
+    fn call(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, &str)]>,
+    ) -> Result<String, String> {
+        self.wait_turn()?;
+        let request = request.set("Cookie", &format!("session={}", self.config.session));
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+        match response {
+            Ok(response) => response
+                .into_string()
+                .map_err(|e| format!("cannot read the answer of the site: {}", e)),
+            Err(ureq::Error::Status(status, response)) => Err(format!(
+                "the site answered {}: {}",
+                status,
+                response.into_string().unwrap_or_default().trim()
+            )),
+            Err(error) => Err(format!("cannot reach the site: {}", error)),
+        }
+    }
+
+    /// Downloads an input to `path`, unless it is already there, maybe encrypted. Returns whether
+    /// it was downloaded.
+    pub fn download_input(&self, year: u32, day: u32, path: &Path) -> Result<bool, String> {
+        if path.exists() || encryption::needs_key(path) {
+            return Ok(false);
+        }
+        let input = self.call(self.agent.get(&self.url(year, day, "/input")), None)?;
+        if let Some(directory) = path.parent() {
+            std::fs::create_dir_all(directory)
+                .map_err(|e| format!("cannot create {}: {}", directory.display(), e))?;
+        }
+        std::fs::write(path, input)
+            .map_err(|e| format!("cannot write {}: {}", path.display(), e))?;
+        Ok(true)
+    }
+
+    pub fn examples(&self, year: u32, day: u32) -> Result<Vec<String>, String> {
+        let page = self.call(self.agent.get(&self.url(year, day, "")), None)?;
+        Ok(extract_examples(&page))
+    }
+
+    /// Submits an answer, unless the history tells it is wrong already, and records the verdict.
+    pub fn submit(
+        &self,
+        year: u32,
+        day: u32,
+        part: u8,
+        answer: &str,
+        history: &mut History,
+    ) -> Result<Verdict, String> {
+        history.check(part, answer)?;
+        let level = part.to_string();
+        let page = self.call(
+            self.agent.post(&self.url(year, day, "/answer")),
+            Some(&[("level", &level), ("answer", answer)]),
+        )?;
+        let verdict = parse_response(&page)?;
+        history.record(part, answer, &verdict);
+        Ok(verdict)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_site::{MockSite, Puzzle, SESSION};
+    use std::sync::atomic::Ordering;
+    use std::time::Instant;
+
+    fn start_site() -> MockSite {
+        MockSite::start(vec![Puzzle {
+            year: 2023,
+            day: 9,
+            input: "0 3 6 9 12 15\n".to_string(),
+            examples: vec!["0 3 6 9 12 15\n1 3 6 10 15 21\n".to_string()],
+            answers: ["114".to_string(), "2".to_string()],
+        }])
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let directory =
+            std::env::temp_dir().join(format!("aoc-client-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&directory);
+        directory
+    }
+
+    fn client(site: &MockSite, directory: &Path, min_interval: Duration) -> Client {
+        Client::new(Config {
+            base_url: site.base_url.clone(),
+            session: SESSION.to_string(),
+            min_interval,
+            stamp_path: directory.join(".last_request"),
+        })
+    }
+
+    #[test]
+    fn test_download_input_once() {
+        let site = start_site();
+        let directory = temp_dir("download");
+        let client = client(&site, &directory, Duration::ZERO);
+        let path = directory.join("day9/input.txt");
+        assert_eq!(client.download_input(2023, 9, &path), Ok(true));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "0 3 6 9 12 15\n");
+        assert_eq!(client.download_input(2023, 9, &path), Ok(false));
+        assert_eq!(site.nb_requests.load(Ordering::SeqCst), 1);
+        assert!(client
+            .download_input(2023, 10, &directory.join("day10/input.txt"))
+            .is_err());
+
+        let logged_out = Client::new(Config {
+            session: "expired".to_string(),
+            ..client.config
+        });
+        let error = logged_out
+            .download_input(2023, 9, &directory.join("day9/other.txt"))
+            .unwrap_err();
+        assert!(error.contains("Please log in"), "{}", error);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let site = start_site();
+        let directory = temp_dir("rate");
+        let client = client(&site, &directory, Duration::from_millis(300));
+        let start = Instant::now();
+        assert_eq!(client.examples(2023, 9).unwrap().len(), 1);
+        // the stamp outlives the client, as it would a run of the command
+        let client = self::client(&site, &directory, Duration::from_millis(300));
+        client.examples(2023, 9).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(300));
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    #[test]
+    fn test_extract_examples() {
+        let page = "<p>For example:</p>\n<pre><code>-L|F7\n7S-7|\nL|7&lt;|\n</code></pre>\n\
+                    <p>The answer is <code><em>4</em></code>, see <pre><code>one line</code></pre></p>\n\
+                    <pre><code>..<em>#</em>.\n&amp;..\n</code></pre>";
+        assert_eq!(
+            extract_examples(page),
+            ["-L|F7\n7S-7|\nL|7<|\n", "..#.\n&..\n"]
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let wait = "You gave an answer too recently; you have to wait after submitting an answer \
+                    before trying again.  You have 2m 5s left to wait.";
+        assert_eq!(
+            parse_response(wait),
+            Ok(Verdict::Wait(Duration::from_secs(125)))
+        );
+        let wait = "You gave an answer too recently. You have 38s left to wait.";
+        assert_eq!(
+            parse_response(wait),
+            Ok(Verdict::Wait(Duration::from_secs(38)))
+        );
+        assert_eq!(
+            parse_response(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Ok(Verdict::WrongLevel)
+        );
+        assert!(parse_response("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_submit_with_history() {
+        let site = start_site();
+        let directory = temp_dir("submit");
+        let client = client(&site, &directory, Duration::ZERO);
+        let mut history = History::default();
+        let mut submit = |answer| client.submit(2023, 9, 1, answer, &mut history);
+        assert_eq!(submit("200"), Ok(Verdict::TooHigh));
+        assert_eq!(submit("100"), Ok(Verdict::TooLow));
+        assert_eq!(submit("wait"), Ok(Verdict::Wait(Duration::from_secs(65))));
+        assert_eq!(submit("abc"), Ok(Verdict::Wrong));
+        let nb_requests = site.nb_requests.load(Ordering::SeqCst);
+        assert!(submit("200").unwrap_err().contains("already submitted"));
+        assert!(submit("250").unwrap_err().contains("too high"));
+        assert!(submit("99").unwrap_err().contains("too low"));
+        assert!(submit("abc").is_err());
+        assert_eq!(site.nb_requests.load(Ordering::SeqCst), nb_requests);
+        assert_eq!(submit("114"), Ok(Verdict::Correct));
+        assert!(submit("115").unwrap_err().contains("already solved"));
+
+        let path = History::path(&directory);
+        std::fs::create_dir_all(&directory).unwrap();
+        history.save(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "1 too_high 200\n1 too_low 100\n1 wrong abc\n1 correct 114\n"
+        );
+        assert_eq!(History::load(&path).unwrap(), history);
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}
@@ -0,0 +1,622 @@
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::Frame;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use crate::cli::{choose_unit, time_function};
+use crate::encryption;
+use crate::fixtures::Manifest;
+use crate::{days, input_root, panic_message, trace};
+
+const SOLVER_THREAD: &str = "solver";
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// An input a day can be run on: an example of the fixtures manifest, with its expected answers,
+/// or a file of the day's inputs directory.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputChoice {
+    Example(usize),
+    File(PathBuf),
+}
+
+pub struct Solved {
+    answer: String,
+    parse_time: Duration,
+    solve_time: Duration,
+    trace: Vec<String>,
+}
+
+enum RunState {
+    Running(Instant, JoinHandle<Result<Solved, String>>),
+    Finished(Result<Solved, String>),
+}
+
+struct Run {
+    choice: usize,
+    state: RunState,
+}
+
+#[derive(Debug, PartialEq)]
+enum Verdict {
+    Running,
+    Right,
+    Wrong,
+    Unchecked,
+    Failed,
+}
+
+struct DayState {
+    year: u32,
+    number: u32,
+    choices: Vec<InputChoice>,
+    choice: usize,
+    runs: [Option<Run>; 2],
+    /// The part whose answer was last shown, and whose trace goes in the panel.
+    last_part: Option<usize>,
+}
+
+pub struct Dashboard {
+    manifest: Arc<Manifest>,
+    days: Vec<DayState>,
+    table: TableState,
+    show_trace: bool,
+    preview: Option<((usize, usize), String)>,
+}
+
+/// Lists the examples of a day, then the files of `inputs_dir/dayN` not already listed as examples.
+pub fn input_choices(
+    manifest: &Manifest,
+    year: u32,
+    day_number: u32,
+    inputs_dir: &Path,
+) -> Vec<InputChoice> {
+    let mut choices = manifest
+        .examples
+        .iter()
+        .enumerate()
+        .filter(|(_, example)| example.year == year && example.day == day_number)
+        .map(|(index, _)| InputChoice::Example(index))
+        .collect::<Vec<_>>();
+
+    let Ok(entries) = std::fs::read_dir(inputs_dir.join(format!("day{}", day_number))) else {
+        return choices;
+    };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let name = name
+                .strip_suffix(&format!(".{}", encryption::EXTENSION))
+                .unwrap_or(name);
+            name.ends_with(".txt").then(|| path.with_file_name(name))
+        })
+        .filter(|path| manifest.find_input(path).is_none())
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths.dedup();
+    choices.extend(paths.into_iter().map(InputChoice::File));
+    choices
+}
+
+fn read_choice(manifest: &Manifest, choice: &InputChoice) -> Result<String, String> {
+    match choice {
+        InputChoice::Example(index) => manifest.examples[*index].read_input(),
+        InputChoice::File(path) => encryption::read_input_file(path),
+    }
+}
+
+fn solve(
+    manifest: &Manifest,
+    year: u32,
+    day_number: u32,
+    choice: &InputChoice,
+    part: usize,
+) -> Result<Solved, String> {
+    let input = read_choice(manifest, choice)?;
+    let (solved, trace) = trace::capture(|| {
+        let (day, parse_time) = time_function(|| match choice {
+            InputChoice::Example(index) => manifest.examples[*index].make_day_from(&input),
+            InputChoice::File(_) => days::make_day(year, day_number, input.as_bytes())
+                .ok_or("day not found".to_string()),
+        });
+        let day = day?;
+        let _part = trace::span("part", &[("number", &part)]);
+        let (answer, solve_time) = time_function(|| match part {
+            1 => day.solution1(),
+            _ => day.solution2(),
+        });
+        Ok::<_, String>((answer, parse_time, solve_time))
+    });
+    let (answer, parse_time, solve_time) = solved?;
+    Ok(Solved {
+        answer,
+        parse_time,
+        solve_time,
+        trace,
+    })
+}
+
+fn format_duration(duration: Duration) -> String {
+    let (time, unit) = choose_unit(duration);
+    format!("{}{}", time, unit)
+}
+
+fn spinner(started: Instant) -> char {
+    SPINNER[(started.elapsed().as_millis() / 100) as usize % SPINNER.len()]
+}
+
+impl Dashboard {
+    /// Lists the days of every year, finding the inputs of a year under `input_root(year)`.
+    pub fn new(manifest: Manifest, input_root: fn(u32) -> PathBuf) -> Dashboard {
+        let days = days::years()
+            .into_iter()
+            .flat_map(|year| {
+                days::day_numbers(year)
+                    .into_iter()
+                    .map(move |number| (year, number))
+            })
+            .map(|(year, number)| DayState {
+                year,
+                number,
+                choices: input_choices(&manifest, year, number, &input_root(year)),
+                choice: 0,
+                runs: [None, None],
+                last_part: None,
+            })
+            .collect();
+        Dashboard {
+            manifest: Arc::new(manifest),
+            days,
+            table: TableState::default().with_selected(0),
+            show_trace: true,
+            preview: None,
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.table.selected().unwrap_or(0)
+    }
+
+    fn expected(&self, day: &DayState, choice: usize, part: usize) -> Option<String> {
+        match day.choices.get(choice)? {
+            InputChoice::Example(index) => self.manifest.examples[*index]
+                .expected(part as u8)
+                .map(str::to_string),
+            InputChoice::File(_) => None,
+        }
+    }
+
+    fn choice_label(&self, day: &DayState, choice: usize) -> String {
+        match day.choices.get(choice) {
+            Some(InputChoice::Example(index)) => self.manifest.examples[*index].name.clone(),
+            Some(InputChoice::File(path)) => path.display().to_string(),
+            None => "no input".to_string(),
+        }
+    }
+
+    fn verdict(&self, day: &DayState, part: usize) -> Option<Verdict> {
+        let run = day.runs[part - 1].as_ref()?;
+        Some(match &run.state {
+            RunState::Running(..) => Verdict::Running,
+            RunState::Finished(Err(_)) => Verdict::Failed,
+            RunState::Finished(Ok(solved)) => match self.expected(day, run.choice, part) {
+                Some(expected) if expected == solved.answer => Verdict::Right,
+                Some(_) => Verdict::Wrong,
+                None => Verdict::Unchecked,
+            },
+        })
+    }
+
+    /// Starts solving a part of the selected day on the selected input, unless it is running.
+    pub fn start(&mut self, part: usize) {
+        let index = self.selected();
+        let day = &mut self.days[index];
+        if matches!(
+            day.runs[part - 1],
+            Some(Run {
+                state: RunState::Running(..),
+                ..
+            })
+        ) {
+            return;
+        }
+        let Some(choice) = day.choices.get(day.choice).cloned() else {
+            return;
+        };
+        let manifest = Arc::clone(&self.manifest);
+        let (year, day_number) = (day.year, day.number);
+        let handle = std::thread::Builder::new()
+            .name(SOLVER_THREAD.to_string())
+            .spawn(move || solve(&manifest, year, day_number, &choice, part))
+            .expect("could start a solver thread");
+        day.runs[part - 1] = Some(Run {
+            choice: day.choice,
+            state: RunState::Running(Instant::now(), handle),
+        });
+        day.last_part = Some(part);
+    }
+
+    /// Collects the results of the runs that are over.
+    pub fn collect_finished(&mut self) {
+        for run in self
+            .days
+            .iter_mut()
+            .flat_map(|day| day.runs.iter_mut())
+            .flatten()
+        {
+            let finished =
+                matches!(&run.state, RunState::Running(_, handle) if handle.is_finished());
+            if finished {
+                let RunState::Running(_, handle) =
+                    std::mem::replace(&mut run.state, RunState::Finished(Err(String::new())))
+                else {
+                    unreachable!()
+                };
+                let result = handle
+                    .join()
+                    .unwrap_or_else(|payload| Err(format!("panicked: {}", panic_message(payload))));
+                run.state = RunState::Finished(result);
+            }
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.days
+            .iter()
+            .flat_map(|day| day.runs.iter().flatten())
+            .any(|run| matches!(run.state, RunState::Running(..)))
+    }
+
+    /// Handles a key, returning false to quit.
+    pub fn on_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => {
+                let next = (self.selected() + 1).min(self.days.len() - 1);
+                self.table.select(Some(next));
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char('i') => {
+                let day = &mut self.days[self.table.selected().unwrap_or(0)];
+                let nb_choices = day.choices.len().max(1);
+                day.choice = match key {
+                    KeyCode::Left => (day.choice + nb_choices - 1) % nb_choices,
+                    _ => (day.choice + 1) % nb_choices,
+                };
+            }
+            KeyCode::Char('1') => self.start(1),
+            KeyCode::Char('2') => self.start(2),
+            KeyCode::Char('t') => self.show_trace = !self.show_trace,
+            _ => {}
+        }
+        true
+    }
+
+    fn part_cells(&self, day: &DayState, part: usize) -> [String; 2] {
+        match day.runs[part - 1].as_ref().map(|run| &run.state) {
+            None => [String::new(), String::new()],
+            Some(RunState::Running(started, _)) => [
+                spinner(*started).to_string(),
+                format_duration(started.elapsed()),
+            ],
+            Some(RunState::Finished(Ok(solved))) => {
+                [solved.answer.clone(), format_duration(solved.solve_time)]
+            }
+            Some(RunState::Finished(Err(_))) => ["error".to_string(), String::new()],
+        }
+    }
+
+    fn status(&self, day: &DayState) -> (&'static str, Color) {
+        let verdicts = [self.verdict(day, 1), self.verdict(day, 2)];
+        let has = |verdict| verdicts.contains(&Some(verdict));
+        if has(Verdict::Running) {
+            ("running", Color::Yellow)
+        } else if has(Verdict::Failed) {
+            ("error", Color::Red)
+        } else if has(Verdict::Wrong) {
+            ("wrong", Color::Red)
+        } else if has(Verdict::Unchecked) {
+            ("unchecked", Color::Blue)
+        } else if has(Verdict::Right) {
+            ("ok", Color::Green)
+        } else {
+            ("", Color::Reset)
+        }
+    }
+
+    fn render_days(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self
+            .days
+            .iter()
+            .map(|day| {
+                let [answer1, time1] = self.part_cells(day, 1);
+                let [answer2, time2] = self.part_cells(day, 2);
+                let (status, color) = self.status(day);
+                Row::new(vec![
+                    ratatui::text::Text::from(format!("{} day {}", day.year, day.number)),
+                    answer1.into(),
+                    time1.into(),
+                    answer2.into(),
+                    time2.into(),
+                    ratatui::text::Text::styled(status, Style::default().fg(color)),
+                ])
+            })
+            .collect::<Vec<_>>();
+        let widths = [
+            Constraint::Length(11),
+            Constraint::Fill(1),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+            Constraint::Length(7),
+            Constraint::Length(9),
+        ];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new(["", "Part 1", "Time", "Part 2", "Time", "Status"])
+                    .style(Style::default().add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(" Days "));
+        frame.render_stateful_widget(table, area, &mut self.table);
+    }
+
+    fn render_details(&self, frame: &mut Frame, area: Rect) {
+        let day = &self.days[self.selected()];
+        let mut lines = vec![Line::from(format!(
+            "Input: {} ({}/{})",
+            self.choice_label(day, day.choice),
+            (day.choice + 1).min(day.choices.len()),
+            day.choices.len()
+        ))];
+        for part in [1, 2] {
+            let expected = self.expected(day, day.choice, part);
+            let line = match day.runs[part - 1].as_ref() {
+                None => format!(
+                    "Part {}: not run, expecting {}",
+                    part,
+                    expected.as_deref().unwrap_or("an unknown answer")
+                ),
+                Some(run) => {
+                    let input = self.choice_label(day, run.choice);
+                    match &run.state {
+                        RunState::Running(started, _) => format!(
+                            "Part {}: {} solving {} for {}",
+                            part,
+                            spinner(*started),
+                            input,
+                            format_duration(started.elapsed())
+                        ),
+                        RunState::Finished(Err(error)) => {
+                            format!("Part {}: {} failed, {}", part, input, error)
+                        }
+                        RunState::Finished(Ok(solved)) => {
+                            let verdict = match self.expected(day, run.choice, part) {
+                                Some(expected) if expected == solved.answer => "right".to_string(),
+                                Some(expected) => format!("wrong, expected {}", expected),
+                                None => "unchecked".to_string(),
+                            };
+                            format!(
+                                "Part {}: {} on {} ({}), parsed in {}, solved in {}",
+                                part,
+                                solved.answer,
+                                input,
+                                verdict,
+                                format_duration(solved.parse_time),
+                                format_duration(solved.solve_time)
+                            )
+                        }
+                    }
+                }
+            };
+            lines.push(Line::from(line));
+        }
+        let details = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(Block::bordered().title(format!(" {} day {} ", day.year, day.number)));
+        frame.render_widget(details, area);
+    }
+
+    fn render_panel(&mut self, frame: &mut Frame, area: Rect) {
+        let index = self.selected();
+        let day = &self.days[index];
+        let solved = day
+            .last_part
+            .and_then(|part| day.runs[part - 1].as_ref())
+            .and_then(|run| match &run.state {
+                RunState::Finished(Ok(solved)) => Some(solved),
+                _ => None,
+            });
+        let (title, text) = match solved {
+            Some(solved) if self.show_trace && !solved.trace.is_empty() => {
+                (" Trace ".to_string(), solved.trace.join("\n"))
+            }
+            _ => {
+                let key = (index, day.choice);
+                if self.preview.as_ref().map(|(shown, _)| *shown) != Some(key) {
+                    let text = match day.choices.get(day.choice) {
+                        Some(choice) => {
+                            read_choice(&self.manifest, choice).unwrap_or_else(|error| error)
+                        }
+                        None => String::new(),
+                    };
+                    self.preview = Some((key, text));
+                }
+                (
+                    " Input ".to_string(),
+                    self.preview.as_ref().unwrap().1.clone(),
+                )
+            }
+        };
+        let panel = Paragraph::new(text).block(Block::bordered().title(title));
+        frame.render_widget(panel, area);
+    }
+
+    pub fn render(&mut self, frame: &mut Frame) {
+        let [main, help] =
+            Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(frame.area());
+        let [days_area, side] =
+            Layout::horizontal([Constraint::Percentage(55), Constraint::Fill(1)]).areas(main);
+        let [details, panel] =
+            Layout::vertical([Constraint::Length(8), Constraint::Fill(1)]).areas(side);
+        self.render_days(frame, days_area);
+        self.render_details(frame, details);
+        self.render_panel(frame, panel);
+        frame.render_widget(
+            Line::from(" ↑/↓ day   ←/→ input   1/2 run part   t trace/input   q quit".to_string()),
+            help,
+        );
+    }
+}
+
+/// Runs the dashboard until the user quits, redrawing it often while solutions are running.
+pub fn run(manifest: Manifest) -> std::io::Result<()> {
+    let mut terminal = ratatui::init();
+    // the panics of the solutions are shown in the dashboard, not printed over it
+    let restore_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() != Some(SOLVER_THREAD) {
+            restore_hook(info);
+        }
+    }));
+
+    let mut dashboard = Dashboard::new(manifest, input_root);
+    let result = loop {
+        dashboard.collect_finished();
+        if let Err(error) = terminal.draw(|frame| dashboard.render(frame)) {
+            break Err(error);
+        }
+        let timeout = if dashboard.is_running() {
+            Duration::from_millis(100)
+        } else {
+            Duration::from_secs(1)
+        };
+        match event::poll(timeout) {
+            Ok(false) => {}
+            Ok(true) => match event::read() {
+                Ok(Event::Key(key)) if key.kind == KeyEventKind::Press => {
+                    if !dashboard.on_key(key.code) {
+                        break Ok(());
+                    }
+                }
+                Ok(_) => {}
+                Err(error) => break Err(error),
+            },
+            Err(error) => break Err(error),
+        }
+    };
+    ratatui::restore();
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    fn wait(dashboard: &mut Dashboard) {
+        while dashboard.is_running() {
+            std::thread::sleep(Duration::from_millis(10));
+            dashboard.collect_finished();
+        }
+    }
+
+    fn screen(dashboard: &mut Dashboard) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(140, 30)).unwrap();
+        terminal.draw(|frame| dashboard.render(frame)).unwrap();
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_input_choices() {
+        let manifest = Manifest::load().unwrap();
+        let inputs_dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        std::fs::create_dir_all(inputs_dir.join("day7")).unwrap();
+        for name in ["input.txt", "slow.txt.enc", "notes.md"] {
+            std::fs::write(inputs_dir.join("day7").join(name), "").unwrap();
+        }
+        let choices = input_choices(&manifest, 2023, 7, &inputs_dir);
+        std::fs::remove_dir_all(&inputs_dir).unwrap();
+
+        let nb_examples = manifest.examples.iter().filter(|e| e.day == 7).count();
+        assert!(choices[..nb_examples]
+            .iter()
+            .all(|choice| matches!(choice, InputChoice::Example(_))));
+        assert_eq!(
+            choices[nb_examples..],
+            [
+                InputChoice::File(inputs_dir.join("day7").join("input.txt")),
+                InputChoice::File(inputs_dir.join("day7").join("slow.txt")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_and_check_parts() {
+        let manifest = Manifest::load().unwrap();
+        let mut dashboard = Dashboard::new(manifest, |_| PathBuf::from("./no-inputs"));
+        // the digits example of day 1 has no answer for part 2, and some lines of the spelled one
+        // have no digit, which part 1 does not expect
+        dashboard.start(1);
+        dashboard.start(2);
+        wait(&mut dashboard);
+        assert_eq!(
+            dashboard.verdict(&dashboard.days[0], 1),
+            Some(Verdict::Right)
+        );
+        assert_eq!(
+            dashboard.verdict(&dashboard.days[0], 2),
+            Some(Verdict::Unchecked)
+        );
+        let screen = screen(&mut dashboard);
+        assert!(
+            screen.contains("Part 1: 142 on digits (right)"),
+            "{}",
+            screen
+        );
+        assert!(screen.contains("unchecked"), "{}", screen);
+
+        assert!(dashboard.on_key(KeyCode::Right));
+        assert!(dashboard.on_key(KeyCode::Char('1')));
+        wait(&mut dashboard);
+        assert_eq!(
+            dashboard.verdict(&dashboard.days[0], 1),
+            Some(Verdict::Failed)
+        );
+        assert!(self::screen(&mut dashboard).contains("Part 1: spelled failed, panicked"));
+
+        assert!(dashboard.on_key(KeyCode::Down));
+        assert!(dashboard.on_key(KeyCode::Char('1')));
+        wait(&mut dashboard);
+        assert_eq!(
+            dashboard.verdict(&dashboard.days[1], 1),
+            Some(Verdict::Right)
+        );
+        assert!(!dashboard.on_key(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_trace_panel() {
+        let manifest = Manifest::load().unwrap();
+        let mut dashboard = Dashboard::new(manifest, |_| PathBuf::from("./no-inputs"));
+        dashboard.table.select(Some(12));
+        dashboard.start(1);
+        wait(&mut dashboard);
+        let screen = screen(&mut dashboard);
+        assert!(screen.contains("island number=1"), "{}", screen);
+        dashboard.on_key(KeyCode::Char('t'));
+        assert!(!self::screen(&mut dashboard).contains("island number=1"));
+    }
+}
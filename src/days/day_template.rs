@@ -1,45 +1,38 @@
-use std::fs::File;
+use rand::Rng;
+use std::io::Read;
 use std::io::BufRead;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
 
 pub struct Day0 {
     data: Vec<String>,
 }
 
 impl Day for Day0 {
-    fn make_day(file: File) -> Self {
-        let data = std::io::BufReader::new(file)
+    fn make_day(input: impl Read) -> Self {
+        let data = std::io::BufReader::new(input)
             .lines()
             .map(|line| line.expect("doc should have lines"))
             .collect();
         Day0 { data }
     }
 
-    fn solution1(&self) -> String {
-        "sol1".to_string()
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::lines("number", r"\d+"))
     }
 
-    fn solution2(&self) -> String {
-        "sol2".to_string()
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| format!("{}\n", rng.gen::<u32>()))
+            .collect()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day0_sol1() {
-        let input = File::open("./inputs/day0/input_test.txt").expect("File not found");
-        let day = Day0::make_day(input);
-        assert_eq!(day.solution1(), "sol1");
+    fn solution1(&self) -> String {
+        "sol1".to_string()
     }
 
-    #[test]
-    fn test_day0_sol2() {
-        let input = File::open("./inputs/day0/input_test.txt").expect("File not found");
-        let day = Day0::make_day(input);
-        assert_eq!(day.solution2(), "sol2");
+    fn solution2(&self) -> String {
+        "sol2".to_string()
     }
 }
@@ -1,30 +1,229 @@
-use std::fs::File;
-
-pub(crate) mod day1;
-pub(crate) mod day10;
-pub(crate) mod day11;
-pub(crate) mod day12;
-pub(crate) mod day13;
-pub(crate) mod day14;
-pub(crate) mod day15;
-pub(crate) mod day16;
-pub(crate) mod day17;
-pub(crate) mod day18;
-pub(crate) mod day19;
-pub(crate) mod day2;
-pub(crate) mod day20;
-pub(crate) mod day21;
-pub(crate) mod day3;
-pub(crate) mod day4;
-pub(crate) mod day5;
-pub(crate) mod day6;
-pub(crate) mod day7;
-pub(crate) mod day8;
-pub(crate) mod day9;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::io::Read;
+
+use crate::lint::{Problem, Spec};
+use crate::normalize::normalize;
+
+pub(crate) mod y2023;
 
 pub trait Day {
-    fn make_day(file: File) -> Self;
+    fn make_day(input: impl Read) -> Self
+    where
+        Self: Sized;
+
+    /// Overrides a puzzle constant (a number of steps, a factor...) hard-coded in the solutions.
+    fn set_param(&mut self, name: &str, _value: &str) -> Result<(), String> {
+        Err(format!("unknown parameter {}", name))
+    }
+
+    /// Describes the shape and the meaning of the inputs, checked by `aoc lint`.
+    fn input_spec() -> Spec
+    where
+        Self: Sized;
+
+    /// Writes a random well-formed input, `size` scaling its number of lines or its grid side.
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String
+    where
+        Self: Sized;
 
     fn solution1(&self) -> String;
     fn solution2(&self) -> String;
+
+    /// The answer of a part, or why the input has none, for days reporting some failures without
+    /// panicking.
+    fn try_solution(&self, part: u8) -> Result<String, String> {
+        Ok(match part {
+            1 => self.solution1(),
+            _ => self.solution2(),
+        })
+    }
+}
+
+macro_rules! register_days {
+    ($($year:literal => { $($number:literal => $day_struct:ty),* $(,)? }),* $(,)?) => {
+        /// Builds a day from its input, normalized first so that the days only see `\n`-terminated
+        /// lines without trailing whitespace.
+        pub fn make_day(year: u32, day_number: u32, mut input: impl Read) -> Option<Box<dyn Day>> {
+            let mut raw_input = String::new();
+            input
+                .read_to_string(&mut raw_input)
+                .expect("input should be utf-8 text");
+            let input = normalize(&raw_input).text;
+            match (year, day_number) {
+                $($(($year, $number) => Some(Box::new(<$day_struct>::make_day(input.as_bytes()))),)*)*
+                _ => None,
+            }
+        }
+
+        /// Lists the problems of an input, without solving it.
+        pub fn lint(year: u32, day_number: u32, input: &str) -> Option<Vec<Problem>> {
+            let input = normalize(input).text;
+            match (year, day_number) {
+                $($(($year, $number) => Some(<$day_struct>::input_spec().lint(&input)),)*)*
+                _ => None,
+            }
+        }
+
+        pub fn has_grid(year: u32, day_number: u32) -> bool {
+            match (year, day_number) {
+                $($(($year, $number) => <$day_struct>::input_spec().has_grid(),)*)*
+                _ => false,
+            }
+        }
+
+        /// The years with registered days, oldest first.
+        pub fn years() -> Vec<u32> {
+            vec![$($year),*]
+        }
+
+        pub fn day_numbers(year: u32) -> Vec<u32> {
+            match year {
+                $($year => vec![$($number),*],)*
+                _ => vec![],
+            }
+        }
+
+        pub fn generate_input(year: u32, day_number: u32, seed: u64, size: usize) -> Option<String> {
+            let mut rng = StdRng::seed_from_u64(seed);
+            match (year, day_number) {
+                $($(($year, $number) => Some(<$day_struct>::generate_input(&mut rng, size)),)*)*
+                _ => None,
+            }
+        }
+    };
+}
+
+register_days! {
+    2023 => {
+        1 => y2023::day1::Day1,
+        2 => y2023::day2::Day2,
+        3 => y2023::day3::Day3,
+        4 => y2023::day4::Day4,
+        5 => y2023::day5::Day5,
+        6 => y2023::day6::Day6,
+        7 => y2023::day7::Day7,
+        8 => y2023::day8::Day8,
+        9 => y2023::day9::Day9,
+        10 => y2023::day10::Day10,
+        11 => y2023::day11::Day11,
+        12 => y2023::day12::Day12,
+        13 => y2023::day13::Day13,
+        14 => y2023::day14::Day14,
+        15 => y2023::day15::Day15,
+        16 => y2023::day16::Day16,
+        17 => y2023::day17::Day17,
+        18 => y2023::day18::Day18,
+        19 => y2023::day19::Day19,
+        20 => y2023::day20::Day20,
+        21 => y2023::day21::Day21,
+    },
+}
+
+/// The year the commands default to when given no `--year`.
+pub fn latest_year() -> u32 {
+    *years().last().expect("some year should be registered")
+}
+
+/// Checks on the inputs a day generates for a few seeds that the model `parse` reads from each of
+/// their non-empty lines, if any, is printed back as a line read into the same model, and as the
+/// very same line when `same_text`.
+#[cfg(test)]
+pub(crate) fn check_round_trip<D: Day, M: PartialEq + std::fmt::Debug + std::fmt::Display>(
+    size: usize,
+    same_text: bool,
+    parse: impl Fn(&str) -> Option<M>,
+) {
+    for seed in 0..20 {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let input = D::generate_input(&mut rng, size);
+        for line in input.lines().filter(|line| !line.is_empty()) {
+            let Some(model) = parse(line) else {
+                continue;
+            };
+            let printed = model.to_string();
+            if same_text {
+                assert_eq!(printed, line);
+            }
+            assert_eq!(parse(&printed), Some(model));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_inputs_are_solvable() {
+        for year in years() {
+            for day_number in day_numbers(year) {
+                for seed in 0..5 {
+                    let input = generate_input(year, day_number, seed, 8).unwrap();
+                    assert_eq!(
+                        lint(year, day_number, &input),
+                        Some(vec![]),
+                        "{} day {}",
+                        year,
+                        day_number
+                    );
+                    let mut day = make_day(year, day_number, input.as_bytes()).unwrap();
+                    // the answers of the bigger inputs may only fit in big integers
+                    let _ = day.set_param("bigint", "true");
+                    day.solution1();
+                    day.solution2();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lint_reports_problems_with_their_line() {
+        let messages = |day_number, input| {
+            lint(2023, day_number, input)
+                .unwrap()
+                .iter()
+                .map(|problem| problem.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(7, "32T3K 765\nT55J5A 684\n"),
+            ["line 2: expected hand"]
+        );
+        assert_eq!(
+            messages(10, "-L|F7\n7|-7|\n"),
+            ["input: expected 1 'S', found 0"]
+        );
+        assert_eq!(
+            messages(21, "...\n.#.\n"),
+            ["input: expected 1 'S', found 0"]
+        );
+        assert_eq!(
+            messages(
+                19,
+                "in{x<10:px,R}\npx{A}\nqs{a>5:lnx,crn}\n\n{x=1,m=2,a=3,s=4}\n"
+            ),
+            [
+                "line 3: unknown workflow lnx",
+                "line 3: unknown workflow crn"
+            ]
+        );
+        assert_eq!(
+            messages(
+                8,
+                "RL\n\nAAA = (BBB, ZZZ)\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n"
+            ),
+            [
+                "line 3: unknown node BBB",
+                "line 4: node AAA already defined on line 3"
+            ]
+        );
+        assert_eq!(
+            messages(
+                4,
+                "Card 1: 41 48 | 83 48\nCard 2: 13 32 | 61 30\nCard 3: 1 2 | 2 1\n"
+            ),
+            ["line 3: 2 matching numbers but only 0 cards after this one"]
+        );
+    }
 }
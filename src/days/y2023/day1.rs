@@ -0,0 +1,857 @@
+use aho_corasick::AhoCorasick;
+use rand::Rng;
+use std::cmp::{max_by_key, min_by_key, Reverse};
+use std::fmt;
+use std::io::BufRead;
+use std::io::Read;
+
+use crate::days::Day;
+use crate::lint::{Block, Spec};
+
+const EXTRACT_WORDS: [(&str, u32); 18] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// Spelled numbers to choose from with the `vocabulary` parameter, the plain digits being always
+/// recognized.
+const VOCABULARIES: [(&str, &[(&str, u32)]); 6] = [
+    ("english", EXTRACT_WORDS.split_at(9).0),
+    (
+        "english-tens",
+        &[
+            ("ten", 10),
+            ("eleven", 11),
+            ("twelve", 12),
+            ("thirteen", 13),
+            ("fourteen", 14),
+            ("fifteen", 15),
+            ("sixteen", 16),
+            ("seventeen", 17),
+            ("eighteen", 18),
+            ("nineteen", 19),
+            ("twenty", 20),
+            ("thirty", 30),
+            ("forty", 40),
+            ("fifty", 50),
+            ("sixty", 60),
+            ("seventy", 70),
+            ("eighty", 80),
+            ("ninety", 90),
+        ],
+    ),
+    (
+        "french",
+        &[
+            ("un", 1),
+            ("deux", 2),
+            ("trois", 3),
+            ("quatre", 4),
+            ("cinq", 5),
+            ("six", 6),
+            ("sept", 7),
+            ("huit", 8),
+            ("neuf", 9),
+            ("dix", 10),
+            ("onze", 11),
+            ("douze", 12),
+            ("treize", 13),
+            ("quatorze", 14),
+            ("quinze", 15),
+            ("seize", 16),
+        ],
+    ),
+    (
+        "german",
+        &[
+            ("eins", 1),
+            ("zwei", 2),
+            ("drei", 3),
+            ("vier", 4),
+            ("fünf", 5),
+            ("sechs", 6),
+            ("sieben", 7),
+            ("acht", 8),
+            ("neun", 9),
+            ("zehn", 10),
+            ("elf", 11),
+            ("zwölf", 12),
+        ],
+    ),
+    (
+        "spanish",
+        &[
+            ("uno", 1),
+            ("dos", 2),
+            ("tres", 3),
+            ("cuatro", 4),
+            ("cinco", 5),
+            ("seis", 6),
+            ("siete", 7),
+            ("ocho", 8),
+            ("nueve", 9),
+            ("diez", 10),
+            ("once", 11),
+            ("doce", 12),
+            ("trece", 13),
+            ("catorce", 14),
+            ("quince", 15),
+        ],
+    ),
+    // numerals are not words, the matcher reads each of them whole
+    ("roman", &[]),
+];
+
+/// The ways each decimal place of a roman numeral is written, thousands first, the index of each
+/// being the digit it stands for.
+const ROMAN_DIGITS: [&[&str]; 4] = [
+    &["", "m", "mm", "mmm"],
+    &["", "c", "cc", "ccc", "cd", "d", "dc", "dcc", "dccc", "cm"],
+    &["", "x", "xx", "xxx", "xl", "l", "lx", "lxx", "lxxx", "xc"],
+    &["", "i", "ii", "iii", "iv", "v", "vi", "vii", "viii", "ix"],
+];
+
+/// The longest numeral, mmmdccclxxxviii.
+const MAX_ROMAN_LENGTH: usize = 15;
+
+fn is_roman(byte: u8) -> bool {
+    b"ivxlcdm".contains(&byte)
+}
+
+/// The value of a numeral written the standard way, from i to mmmcmxcix, read one decimal place
+/// after the other. The letters of each place never start the next places, so the longest way of
+/// writing it is the only one which can be followed by the rest.
+fn roman_value(numeral: &str) -> Option<u32> {
+    let mut rest = numeral;
+    let mut value = 0;
+    for digits in ROMAN_DIGITS {
+        let (digit, written) = digits
+            .iter()
+            .enumerate()
+            .filter(|(_, written)| rest.starts_with(**written))
+            .max_by_key(|(_, written)| written.len())
+            .expect("a place can be left out");
+        value = 10 * value + digit as u32;
+        rest = &rest[written.len()..];
+    }
+    (rest.is_empty() && value > 0).then_some(value)
+}
+
+/// The numerals of a line, only read in the runs of letters made of roman ones alone so that the
+/// letters of words ("six", "five") are not taken for numerals. Each run is split into the longest
+/// numerals starting it, so that "xviii" is 18 and "ixi" is 9 then 1.
+fn find_roman_numerals(line: &str) -> impl Iterator<Item = FoundNumber> + '_ {
+    // the bytes of letters outside ASCII belong to words too
+    let is_letter = |byte: &u8| byte.is_ascii_alphabetic() || !byte.is_ascii();
+    let bytes = line.as_bytes();
+    let (mut offset, mut run_end) = (0, 0);
+    std::iter::from_fn(move || loop {
+        while offset >= run_end {
+            offset = run_end + bytes[run_end..].iter().position(is_letter)?;
+            run_end = offset + bytes[offset..].iter().take_while(|b| is_letter(b)).count();
+            if !bytes[offset..run_end].iter().all(|byte| is_roman(*byte)) {
+                offset = run_end;
+            }
+        }
+        let longest = (1..=MAX_ROMAN_LENGTH.min(run_end - offset))
+            .rev()
+            .find_map(|length| Some((length, roman_value(&line[offset..offset + length])?)));
+        match longest {
+            Some((length, value)) => {
+                let found = FoundNumber {
+                    offset,
+                    length,
+                    value,
+                };
+                offset += length;
+                return Some(found);
+            }
+            None => offset += 1,
+        }
+    })
+}
+
+/// Reads `word value` lines, skipping blank ones and `#` comments.
+fn parse_vocabulary(text: &str) -> Result<Vec<(String, u32)>, String> {
+    text.lines()
+        .enumerate()
+        .map(|(index, line)| (index, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(index, line)| {
+            line.split_once(char::is_whitespace)
+                .and_then(|(word, value)| Some((word.to_string(), value.trim().parse().ok()?)))
+                .ok_or_else(|| format!("line {} should be a word and its value", index + 1))
+        })
+        .collect()
+}
+
+/// The words of comma-separated vocabularies, each being a built-in one or a file of them.
+fn load_vocabularies(names: &str) -> Result<Vec<(String, u32)>, String> {
+    let mut words = vec![];
+    for name in names.split(',') {
+        match VOCABULARIES.iter().find(|(builtin, _)| *builtin == name) {
+            Some((_, builtin_words)) => words.extend(
+                builtin_words
+                    .iter()
+                    .map(|(word, value)| (word.to_string(), *value)),
+            ),
+            None => {
+                let text = std::fs::read_to_string(name).map_err(|_| {
+                    format!(
+                        "{} is neither a vocabulary ({}) nor a readable file",
+                        name,
+                        VOCABULARIES.map(|(builtin, _)| builtin).join(", ")
+                    )
+                })?;
+                words.extend(parse_vocabulary(&text).map_err(|e| format!("{}: {}", name, e))?);
+            }
+        }
+    }
+    Ok(words)
+}
+
+pub struct Day1 {
+    document_lines: Vec<String>,
+    matcher: DigitMatcher,
+}
+
+/// Finds every word of a table in a line in a single scan, overlapping ones included, so that
+/// "eightwo" gives both 8 and 2. The values of words can have several digits.
+struct DigitMatcher {
+    automaton: AhoCorasick,
+    /// The value of each word, by pattern index.
+    values: Vec<u32>,
+    roman_numerals: bool,
+}
+
+/// A number found on a line, with the byte offset and the length of the word or digit it was
+/// written as.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct FoundNumber {
+    offset: usize,
+    length: usize,
+    value: u32,
+}
+
+impl FoundNumber {
+    fn text<'a>(&self, line: &'a str) -> &'a str {
+        &line[self.offset..self.offset + self.length]
+    }
+
+    fn is_digit(&self, line: &str) -> bool {
+        self.length == 1 && line.as_bytes()[self.offset].is_ascii_digit()
+    }
+}
+
+impl DigitMatcher {
+    fn new(words: &[(impl AsRef<str>, u32)]) -> DigitMatcher {
+        let automaton = AhoCorasick::new(words.iter().map(|(word, _)| word.as_ref()))
+            .expect("the words should build an automaton");
+        DigitMatcher {
+            automaton,
+            values: words.iter().map(|(_, value)| *value).collect(),
+            roman_numerals: false,
+        }
+    }
+
+    fn reading_roman_numerals(self) -> DigitMatcher {
+        DigitMatcher {
+            roman_numerals: true,
+            ..self
+        }
+    }
+
+    /// Every word and numeral of a line, several of them possibly starting at the same place.
+    fn matches<'a>(&'a self, line: &'a str) -> impl Iterator<Item = FoundNumber> + 'a {
+        let words = self
+            .automaton
+            .find_overlapping_iter(line)
+            .map(|found| FoundNumber {
+                offset: found.start(),
+                length: found.len(),
+                value: self.values[found.pattern().as_usize()],
+            });
+        let numerals = self
+            .roman_numerals
+            .then(|| find_roman_numerals(line))
+            .into_iter()
+            .flatten();
+        words.chain(numerals)
+    }
+
+    #[cfg(test)]
+    fn extract_numbers_from_string(&self, line: &str) -> Vec<u32> {
+        self.find_numbers(line)
+            .into_iter()
+            .map(|found| found.value)
+            .collect()
+    }
+
+    /// The words and numerals found, in the order they start, the longest one winning when
+    /// several start at the same place ("eighteen" over "eight").
+    fn find_numbers(&self, line: &str) -> Vec<FoundNumber> {
+        let mut matches = self.matches(line).collect::<Vec<_>>();
+        matches.sort_by_key(|found| (found.offset, Reverse(found.length)));
+        matches.dedup_by_key(|found| found.offset);
+        matches
+    }
+
+    /// The first and the last of the numbers `find_numbers` gives, found in a single pass without
+    /// keeping the others.
+    fn first_and_last(&self, line: &str) -> Option<(FoundNumber, FoundNumber)> {
+        let mut matches = self.matches(line);
+        let found = matches.next()?;
+        Some(matches.fold((found, found), |(first, last), found| {
+            (
+                min_by_key(first, found, |found| (found.offset, Reverse(found.length))),
+                max_by_key(last, found, |found| (found.offset, found.length)),
+            )
+        }))
+    }
+}
+
+fn calibration_value(line: &str) -> Option<u32> {
+    let mut digits = line.chars().filter_map(|c| c.to_digit(10));
+    let first_digit = digits.next()?;
+    let last_digit = digits.next_back().unwrap_or(first_digit);
+    Some(10 * first_digit + last_digit)
+}
+
+fn calibration_value2(line: &str, matcher: &DigitMatcher) -> Option<u32> {
+    let (first_number, last_number) = matcher.first_and_last(line)?;
+    // the first digit of the first number and the last digit of the last one
+    let first_digit = first_number
+        .value
+        .to_string()
+        .chars()
+        .next()
+        .and_then(|c| c.to_digit(10))
+        .expect("a number has digits");
+    Some(10 * first_digit + last_number.value % 10)
+}
+
+fn get_number_from_line(line: &str) -> u32 {
+    calibration_value(line).unwrap_or_else(|| panic!("no digit on line {}", line))
+}
+
+fn get_number_from_line2(line: &str, matcher: &DigitMatcher) -> u32 {
+    calibration_value2(line, matcher).unwrap_or_else(|| panic!("no digit on line {}", line))
+}
+
+/// The matcher of the `vocabulary` parameter, plain digits included.
+fn vocabulary_matcher(names: &str) -> Result<DigitMatcher, String> {
+    let mut words = load_vocabularies(names)?;
+    words.extend(
+        EXTRACT_WORDS[9..]
+            .iter()
+            .map(|(digit, value)| (digit.to_string(), *value)),
+    );
+    let matcher = DigitMatcher::new(&words);
+    match names.split(',').any(|name| name == "roman") {
+        true => Ok(matcher.reading_roman_numerals()),
+        false => Ok(matcher),
+    }
+}
+
+/// The parameters of part 2, shared by the day and the readers of documents.
+fn set_matcher_param(matcher: &mut DigitMatcher, name: &str, value: &str) -> Result<(), String> {
+    match name {
+        // comma-separated built-in vocabularies or files of `word value` lines
+        "vocabulary" => {
+            *matcher = vocabulary_matcher(value)?;
+            Ok(())
+        }
+        _ => Err(format!("unknown parameter {}", name)),
+    }
+}
+
+/// The calibration value of a line read by [`Calibration`], with the total so far.
+pub struct CalibratedLine {
+    pub line_number: usize,
+    pub value: u32,
+    pub total: u64,
+}
+
+/// Calibration values of a document read line by line, keeping only their running total, so that
+/// documents of any length can be summed, blank lines being skipped.
+pub struct Calibration<R> {
+    lines: std::io::Lines<R>,
+    /// The words of part 2, none for part 1.
+    matcher: Option<DigitMatcher>,
+    line_number: usize,
+    total: u64,
+}
+
+impl<R: BufRead> Calibration<R> {
+    pub fn new(reader: R, part: u8) -> Result<Calibration<R>, String> {
+        let matcher = match part {
+            1 => None,
+            2 => Some(DigitMatcher::new(&EXTRACT_WORDS)),
+            _ => return Err(format!("part should be 1 or 2, not {}", part)),
+        };
+        Ok(Calibration {
+            lines: reader.lines(),
+            matcher,
+            line_number: 0,
+            total: 0,
+        })
+    }
+
+    /// Takes the same parameters as the day.
+    pub fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match &mut self.matcher {
+            Some(matcher) => set_matcher_param(matcher, name, value),
+            None if name == "vocabulary" => Err("vocabularies only apply to part 2".to_string()),
+            None => Err(format!("unknown parameter {}", name)),
+        }
+    }
+
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+}
+
+/// The next line which is not blank, counting the lines read.
+fn next_filled_line(
+    lines: &mut std::io::Lines<impl BufRead>,
+    line_number: &mut usize,
+) -> Option<Result<String, String>> {
+    loop {
+        let line = match lines.next()? {
+            Ok(line) => line,
+            Err(error) => return Some(Err(format!("cannot read the document: {}", error))),
+        };
+        *line_number += 1;
+        let line = line.trim_end();
+        if !line.is_empty() {
+            return Some(Ok(line.to_string()));
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Calibration<R> {
+    type Item = Result<CalibratedLine, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match next_filled_line(&mut self.lines, &mut self.line_number)? {
+            Ok(line) => line,
+            Err(error) => return Some(Err(error)),
+        };
+        let value = match &self.matcher {
+            None => calibration_value(&line),
+            Some(matcher) => calibration_value2(&line, matcher),
+        };
+        let Some(value) = value else {
+            return Some(Err(format!("no digit on line {}", self.line_number)));
+        };
+        self.total += value as u64;
+        Some(Ok(CalibratedLine {
+            line_number: self.line_number,
+            value,
+            total: self.total,
+        }))
+    }
+}
+
+/// What was found on a line of a calibration document and the values both parts give it.
+pub struct LineDiagnostic {
+    pub line_number: usize,
+    line: String,
+    found: Vec<FoundNumber>,
+    pub values: [Option<u32>; 2],
+}
+
+impl LineDiagnostic {
+    fn new(line_number: usize, line: String, matcher: &DigitMatcher) -> LineDiagnostic {
+        let values = [calibration_value(&line), calibration_value2(&line, matcher)];
+        LineDiagnostic {
+            line_number,
+            found: matcher.find_numbers(&line),
+            line,
+            values,
+        }
+    }
+
+    pub fn has_digits(&self) -> bool {
+        self.values.iter().any(Option::is_some)
+    }
+
+    pub fn parts_differ(&self) -> bool {
+        self.values[0] != self.values[1]
+    }
+}
+
+impl fmt::Display for LineDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "line {}: {}", self.line_number, self.line)?;
+        if !self.has_digits() {
+            return write!(f, "  warning: no digit");
+        }
+        let found = self
+            .found
+            .iter()
+            .map(|found| match found.is_digit(&self.line) {
+                true => format!("{} digit at {}", found.value, found.offset),
+                false => format!(
+                    "{} word \"{}\" at {}",
+                    found.value,
+                    found.text(&self.line),
+                    found.offset
+                ),
+            })
+            .collect::<Vec<_>>();
+        writeln!(f, "  {}", found.join(", "))?;
+        let value = |value: Option<u32>| value.map_or("no digit".to_string(), |v| v.to_string());
+        write!(
+            f,
+            "  part 1: {}, part 2: {}{}",
+            value(self.values[0]),
+            value(self.values[1]),
+            if self.parts_differ() {
+                "  <- parts differ"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Diagnostics of each line of a calibration document read line by line, as [`Calibration`]
+/// reads it, lines without digits being reported instead of failing.
+pub struct Diagnostics<R> {
+    lines: std::io::Lines<R>,
+    matcher: DigitMatcher,
+    line_number: usize,
+}
+
+impl<R: BufRead> Diagnostics<R> {
+    pub fn new(reader: R) -> Diagnostics<R> {
+        Diagnostics {
+            lines: reader.lines(),
+            matcher: DigitMatcher::new(&EXTRACT_WORDS),
+            line_number: 0,
+        }
+    }
+
+    /// Takes the same parameters as the day.
+    pub fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        set_matcher_param(&mut self.matcher, name, value)
+    }
+}
+
+impl<R: BufRead> Iterator for Diagnostics<R> {
+    type Item = Result<LineDiagnostic, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let diagnostic = next_filled_line(&mut self.lines, &mut self.line_number)?
+            .map(|line| LineDiagnostic::new(self.line_number, line, &self.matcher));
+        Some(diagnostic)
+    }
+}
+
+impl Day for Day1 {
+    fn make_day(input: impl Read) -> Self {
+        let document_lines = std::io::BufReader::new(input)
+            .lines()
+            .map(|line| line.expect("doc should have lines"))
+            .collect();
+        Day1 {
+            document_lines,
+            matcher: DigitMatcher::new(&EXTRACT_WORDS),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        set_matcher_param(&mut self.matcher, name, value)
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::lines("calibration line", "[a-z0-9]+"))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| {
+                let mut line = String::new();
+                // every line holds at least one plain digit so that both parts can solve it
+                let digit_position = rng.gen_range(0..8);
+                for position in 0..8 {
+                    if position == digit_position || rng.gen_bool(0.1) {
+                        line.push(char::from(b'1' + rng.gen_range(0..9)));
+                    } else if rng.gen_bool(0.2) {
+                        line.push_str(EXTRACT_WORDS[rng.gen_range(0..9)].0);
+                    } else {
+                        line.push(char::from(b'a' + rng.gen_range(0..26)));
+                    }
+                }
+                line + "\n"
+            })
+            .collect()
+    }
+
+    fn solution1(&self) -> String {
+        let result: u32 = self
+            .document_lines
+            .iter()
+            .map(|line| get_number_from_line(line))
+            .sum();
+        result.to_string()
+    }
+
+    fn solution2(&self) -> String {
+        let result: u32 = self
+            .document_lines
+            .iter()
+            .map(|line| get_number_from_line2(line, &self.matcher))
+            .sum();
+        result.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_day1_sol1() {
+        let document_lines = vec![
+            "1abc2".to_string(),
+            "pqr3stu8vwx".to_string(),
+            "a1b2c3d4e5f".to_string(),
+            "treb7uchet".to_string(),
+        ];
+        let day1 = Day1 {
+            document_lines,
+            matcher: DigitMatcher::new(&EXTRACT_WORDS),
+        };
+        assert_eq!(day1.solution1(), "142");
+    }
+
+    #[test]
+    fn test_day1_sol2() {
+        let document_lines = vec![
+            "two1nine".to_string(),
+            "eightwothree".to_string(),
+            "abcone2threexyz".to_string(),
+            "xtwone3four".to_string(),
+            "4nineeightseven2".to_string(),
+            "zoneight234".to_string(),
+            "7pqrstsixteen".to_string(),
+        ];
+        let day1 = Day1 {
+            document_lines,
+            matcher: DigitMatcher::new(&EXTRACT_WORDS),
+        };
+        assert_eq!(day1.solution2(), "281");
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let matcher = DigitMatcher::new(&EXTRACT_WORDS);
+        assert_eq!(matcher.extract_numbers_from_string("eightwo"), vec![8, 2]);
+        assert_eq!(
+            matcher.extract_numbers_from_string("oneighthreeightwone7"),
+            vec![1, 8, 3, 8, 2, 1, 7]
+        );
+        assert_eq!(get_number_from_line2("xtwone", &matcher), 21);
+
+        // the first and the last numbers of a single pass are those of the sorted matches
+        let matcher = vocabulary_matcher("english,english-tens").unwrap();
+        let first_and_last = |line| {
+            let (first, last) = matcher.first_and_last(line).unwrap();
+            (first.value, last.value)
+        };
+        assert_eq!(first_and_last("twoneighteen"), (2, 18));
+        assert_eq!(first_and_last("sixteen3seventy"), (16, 70));
+        let mut rng = rand::rngs::StdRng::seed_from_u64(1);
+        for line in Day1::generate_input(&mut rng, 200).lines() {
+            let found = matcher.find_numbers(line);
+            let expected = (*found.first().unwrap(), *found.last().unwrap());
+            assert_eq!(matcher.first_and_last(line), Some(expected), "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_long_line() {
+        let line = "two".to_string() + &"oneightx".repeat(1 << 18) + "sevenine";
+        let matcher = DigitMatcher::new(&EXTRACT_WORDS);
+        assert_eq!(get_number_from_line2(&line, &matcher), 29);
+    }
+
+    #[test]
+    fn test_vocabularies() {
+        let mut day1 = Day1::make_day("deuxfivezwei\n3sieteonze\n".as_bytes());
+        day1.set_param("vocabulary", "french,german,spanish")
+            .unwrap();
+        // five is not a word of these, and onze stands for 11
+        assert_eq!(day1.solution2(), "53");
+
+        let mut day1 = Day1::make_day("seventeenx\ntwentyone\neightyeight\n".as_bytes());
+        day1.set_param("vocabulary", "english,english-tens")
+            .unwrap();
+        assert_eq!(day1.solution2(), (17 + 21 + 88).to_string());
+
+        day1.set_param("vocabulary", "roman").unwrap();
+        assert_eq!(roman_value("mmmcmxcix"), Some(3999));
+        assert_eq!(roman_value("iiii"), None);
+        assert_eq!(roman_value("vx"), None);
+        assert_eq!(roman_value("ivi"), None);
+        assert_eq!(roman_value("mcmxc"), Some(1990));
+        assert_eq!(roman_value(""), None);
+        assert_eq!(day1.matcher.extract_numbers_from_string("xviii"), [18]);
+        assert_eq!(day1.matcher.extract_numbers_from_string("ixi"), [9, 1]);
+        assert_eq!(
+            day1.matcher.extract_numbers_from_string("7mmxxiiii3"),
+            [7, 2023, 1, 3]
+        );
+        assert_eq!(day1.matcher.extract_numbers_from_string("abcxii7"), [7]);
+        day1.set_param("vocabulary", "roman,english").unwrap();
+        // the letters of words are not numerals
+        for (word, value) in [("six", 6), ("five", 5), ("seven", 7), ("eight", 8)] {
+            assert_eq!(day1.matcher.extract_numbers_from_string(word), [value]);
+        }
+        assert_eq!(
+            day1.matcher.extract_numbers_from_string("sixteen4vi"),
+            [6, 4, 6]
+        );
+
+        let err = day1.set_param("vocabulary", "english,klingon").unwrap_err();
+        assert!(err.starts_with("klingon is neither a vocabulary"));
+    }
+
+    #[test]
+    fn test_vocabulary_file() {
+        assert_eq!(
+            parse_vocabulary("# numbers\n\nzero 0\nhundred  100\n"),
+            Ok(vec![("zero".to_string(), 0), ("hundred".to_string(), 100)])
+        );
+        assert_eq!(
+            parse_vocabulary("zero\n"),
+            Err("line 1 should be a word and its value".to_string())
+        );
+
+        let path = std::env::temp_dir().join(format!("aoc-vocabulary-{}", std::process::id()));
+        std::fs::write(&path, "zero 0\nhundred 100\n").unwrap();
+        let mut day1 = Day1::make_day("hundredone\nzero7\n".as_bytes());
+        let result = day1.set_param("vocabulary", &format!("english,{}", path.display()));
+        std::fs::remove_file(&path).unwrap();
+        result.unwrap();
+        assert_eq!(day1.solution2(), (11 + 7).to_string());
+    }
+
+    /// Lines of a document made up on the fly, never held in memory as a whole.
+    struct RepeatedLines {
+        line: &'static [u8],
+        nb_lines: usize,
+        position: usize,
+    }
+
+    impl Read for RepeatedLines {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let mut written = 0;
+            while written < buf.len() && self.position < self.line.len() * self.nb_lines {
+                buf[written] = self.line[self.position % self.line.len()];
+                written += 1;
+                self.position += 1;
+            }
+            Ok(written)
+        }
+    }
+
+    #[test]
+    fn test_calibration() {
+        let document = "two1nine\n\neightwothree\r\nabc\n7pqrstsixteen\n";
+        let mut calibration = Calibration::new(document.as_bytes(), 2).unwrap();
+        let results = calibration
+            .by_ref()
+            .map(|line| line.map(|line| (line.line_number, line.value, line.total)))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                Ok((1, 29, 29)),
+                Ok((3, 83, 112)),
+                Err("no digit on line 4".to_string()),
+                Ok((5, 76, 188))
+            ]
+        );
+        assert_eq!(calibration.total(), 188);
+
+        let mut calibration = Calibration::new(document.as_bytes(), 1).unwrap();
+        assert_eq!(
+            calibration
+                .set_param("vocabulary", "french")
+                .map_err(|_| ()),
+            Err(())
+        );
+        let results = calibration
+            .map(|line| line.map(|line| line.value))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            results,
+            vec![
+                Ok(11),
+                Err("no digit on line 3".to_string()),
+                Err("no digit on line 4".to_string()),
+                Ok(77)
+            ]
+        );
+        assert_eq!(
+            Calibration::new(document.as_bytes(), 3).err(),
+            Some("part should be 1 or 2, not 3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_streaming_calibration() {
+        let document = RepeatedLines {
+            line: b"xtwone3four\n",
+            nb_lines: 500_000,
+            position: 0,
+        };
+        let mut calibration = Calibration::new(std::io::BufReader::new(document), 2).unwrap();
+        assert_eq!(calibration.by_ref().filter(Result::is_err).count(), 0);
+        assert_eq!(calibration.total(), 24 * 500_000);
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let document = "two1nine\nabc\n4nineeightseven2\n";
+        let diagnostics = Diagnostics::new(document.as_bytes())
+            .map(|diagnostic| diagnostic.unwrap().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            vec![
+                "line 1: two1nine\n  2 word \"two\" at 0, 1 digit at 3, 9 word \"nine\" at 4\n  \
+                 part 1: 11, part 2: 29  <- parts differ",
+                "line 2: abc\n  warning: no digit",
+                "line 3: 4nineeightseven2\n  4 digit at 0, 9 word \"nine\" at 1, 8 word \"eight\" at 5, \
+                 7 word \"seven\" at 10, 2 digit at 15\n  part 1: 42, part 2: 42",
+            ]
+        );
+
+        let mut diagnostics = Diagnostics::new("drei3eightwo\n".as_bytes());
+        diagnostics.set_param("vocabulary", "german").unwrap();
+        let diagnostic = diagnostics.next().unwrap().unwrap();
+        assert!(diagnostic.has_digits() && !diagnostic.parts_differ());
+        assert_eq!(diagnostic.values, [Some(33), Some(33)]);
+    }
+}
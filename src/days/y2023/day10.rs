@@ -1,8 +1,12 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::collections::HashMap;
-use std::fs::File;
+use std::collections::HashSet;
 use std::io::BufRead;
+use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
 
 #[derive(Debug)]
 pub struct Day10 {
@@ -58,7 +62,7 @@ impl<'a> Walkers<'a> {
             Direction::North,
         ]
         .into_iter()
-        .zip(["A", "B", "C", "D"].into_iter())
+        .zip(["A", "B", "C", "D"])
         .map(|(dir, id)| {
             let (row, col) = dir.to_next_tile(day.start_pos);
             let tile = day.get_tile(row, col);
@@ -67,7 +71,7 @@ impl<'a> Walkers<'a> {
                     curr_pos: (row as usize, col as usize),
                     is_still_walking: true,
                     last_move: dir,
-                    curr_tile: tile.unwrap().clone(),
+                    curr_tile: *tile.unwrap(),
                     id: id.to_string(),
                 }
             } else {
@@ -120,7 +124,7 @@ impl<'a> Walkers<'a> {
                         curr_pos: (row as usize, col as usize),
                         is_still_walking: true,
                         last_move: dir_to_take,
-                        curr_tile: tile.unwrap().clone(),
+                        curr_tile: *tile.unwrap(),
                         id: walker.id.to_string(),
                     }
                 } else {
@@ -209,7 +213,7 @@ impl<'a> Walkers<'a> {
     }
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
 enum Direction {
     North,
     West,
@@ -218,7 +222,7 @@ enum Direction {
 }
 
 impl Direction {
-    fn to_next_tile(&self, pos: (usize, usize)) -> (isize, isize) {
+    fn to_next_tile(self, pos: (usize, usize)) -> (isize, isize) {
         let (r, c) = (pos.0 as isize, pos.1 as isize);
         match self {
             Direction::North => (r - 1, c),
@@ -328,10 +332,50 @@ fn is_inside(walls: &HashMap<(usize, usize), Tile>, pos: (usize, usize)) -> bool
     is_crossing
 }
 
+fn random_spanning_tree(
+    rng: &mut impl Rng,
+    nb_cells: usize,
+) -> HashSet<((usize, usize), Direction)> {
+    // randomized depth-first search, each edge is stored from its north or west cell
+    let mut visited = vec![vec![false; nb_cells]; nb_cells];
+    let mut edges = HashSet::new();
+    let mut stack = vec![(0, 0)];
+    visited[0][0] = true;
+    while let Some(&cell) = stack.last() {
+        let unvisited = [
+            Direction::North,
+            Direction::West,
+            Direction::South,
+            Direction::East,
+        ]
+        .into_iter()
+        .filter_map(|dir| {
+            let (r, c) = dir.to_next_tile(cell);
+            let (r, c) = (usize::try_from(r).ok()?, usize::try_from(c).ok()?);
+            (r < nb_cells && c < nb_cells && !visited[r][c]).then_some((dir, (r, c)))
+        })
+        .collect::<Vec<_>>();
+        match unvisited.choose(rng) {
+            Some(&(dir, next)) => {
+                visited[next.0][next.1] = true;
+                match dir {
+                    Direction::North | Direction::West => edges.insert((next, dir.opposite())),
+                    Direction::South | Direction::East => edges.insert((cell, dir)),
+                };
+                stack.push(next);
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    edges
+}
+
 impl Day for Day10 {
-    fn make_day(file: File) -> Self {
+    fn make_day(input: impl Read) -> Self {
         let mut start_pos = (0, 0);
-        let field: Vec<Vec<Tile>> = std::io::BufReader::new(file)
+        let field: Vec<Vec<Tile>> = std::io::BufReader::new(input)
             .lines()
             .enumerate()
             .map(|(y, line)| {
@@ -350,14 +394,109 @@ impl Day for Day10 {
         Day10 { field, start_pos }
     }
 
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(Block::grid("pipe map", "[|\\-LJ7F.S]"))
+            .count('S', 1)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // Going around a random spanning tree of coarse cells gives a loop through every cell of
+        // a grid twice as large, which is stretched once more so that the loop encloses ground.
+        let nb_coarse = (size / 4).max(1);
+        let side = 2 * nb_coarse;
+        let tree_edges = random_spanning_tree(rng, nb_coarse);
+        let is_linked = |(i, j): (usize, usize), dir: Direction| match dir {
+            Direction::South | Direction::East => tree_edges.contains(&((i, j), dir)),
+            Direction::North => i > 0 && tree_edges.contains(&((i - 1, j), Direction::South)),
+            Direction::West => j > 0 && tree_edges.contains(&((i, j - 1), Direction::East)),
+        };
+
+        let mut connections = vec![vec![vec![]; side]; side];
+        let mut connect = |(r1, c1): (usize, usize), (r2, c2): (usize, usize)| {
+            let dir = if r2 > r1 {
+                Direction::South
+            } else if c2 > c1 {
+                Direction::East
+            } else {
+                panic!("cells should be given from north or west")
+            };
+            connections[r1][c1].push(dir);
+            connections[r2][c2].push(dir.opposite());
+        };
+        for i in 0..nb_coarse {
+            for j in 0..nb_coarse {
+                let (r, c) = (2 * i, 2 * j);
+                if !is_linked((i, j), Direction::North) {
+                    connect((r, c), (r, c + 1));
+                }
+                if !is_linked((i, j), Direction::West) {
+                    connect((r, c), (r + 1, c));
+                }
+                if is_linked((i, j), Direction::South) {
+                    connect((r + 1, c), (r + 2, c));
+                    connect((r + 1, c + 1), (r + 2, c + 1));
+                } else {
+                    connect((r + 1, c), (r + 1, c + 1));
+                }
+                if is_linked((i, j), Direction::East) {
+                    connect((r, c + 1), (r, c + 2));
+                    connect((r + 1, c + 1), (r + 1, c + 2));
+                } else {
+                    connect((r, c + 1), (r + 1, c + 1));
+                }
+            }
+        }
+
+        let mut field = vec![vec!['.'; 2 * side]; 2 * side];
+        let mut stretched_vertical_pipes = vec![];
+        for (r, row) in connections.iter().enumerate() {
+            for (c, dirs) in row.iter().enumerate() {
+                let has = |dir| dirs.contains(&dir);
+                field[2 * r][2 * c] = match (has(Direction::North), has(Direction::South)) {
+                    (true, true) => '|',
+                    (true, false) if has(Direction::East) => 'L',
+                    (true, false) => 'J',
+                    (false, true) if has(Direction::West) => '7',
+                    (false, true) => 'F',
+                    (false, false) => '-',
+                };
+                if has(Direction::South) {
+                    field[2 * r + 1][2 * c] = '|';
+                    stretched_vertical_pipes.push((2 * r + 1, 2 * c));
+                }
+                if has(Direction::East) {
+                    field[2 * r][2 * c + 1] = '-';
+                }
+            }
+        }
+        for tile in field.iter_mut().flatten() {
+            if *tile == '.' && rng.gen_bool(0.3) {
+                *tile = ['|', '-', 'L', 'J', '7', 'F'][rng.gen_range(0..6)];
+            }
+        }
+        // on a stretched pipe, the start only has loop tiles above and below it
+        let (r, c) = stretched_vertical_pipes[rng.gen_range(0..stretched_vertical_pipes.len())];
+        field[r][c] = 'S';
+        field[r][c + 1] = '.';
+        if c > 0 {
+            field[r][c - 1] = '.';
+        }
+
+        field
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
+
     fn solution1(&self) -> String {
-        let mut walkers = Walkers::create_walkers(&self);
+        let mut walkers = Walkers::create_walkers(self);
         let result = walkers.walk_until_finished();
         result.to_string()
     }
 
     fn solution2(&self) -> String {
-        let mut walkers = Walkers::create_walkers(&self);
+        let mut walkers = Walkers::create_walkers(self);
         let map = walkers.walk_until_loop_build();
         let mut result = 0;
         for r in 0..self.field.len() {
@@ -370,29 +509,3 @@ impl Day for Day10 {
         result.to_string()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day10_sol1() {
-        let input = File::open("./inputs/day10/input_test.txt").expect("File not found");
-        let day = Day10::make_day(input);
-        assert_eq!(day.solution1(), "8");
-    }
-
-    #[test]
-    fn test_day10_sol2() {
-        let input = File::open("./inputs/day10/input_test.txt").expect("File not found");
-        let day = Day10::make_day(input);
-        assert_eq!(day.solution2(), "sol2");
-    }
-
-    #[test]
-    fn test_day10_sol2_2() {
-        let input = File::open("./inputs/day10/input_test2.txt").expect("File not found");
-        let day = Day10::make_day(input);
-        assert_eq!(day.solution2(), "4");
-    }
-}
@@ -1,12 +1,15 @@
-use std::fs::File;
+use rand::Rng;
 use std::io::BufRead;
+use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
 
 type Coordinate = (usize, usize);
 
 pub struct Day11 {
     galaxies: Vec<Coordinate>,
+    expansion_factor: usize,
 }
 
 impl Day11 {
@@ -48,18 +51,18 @@ fn get_missing_values(mut vec: Vec<usize>) -> Vec<usize> {
 fn distance(
     p1: Coordinate,
     p2: Coordinate,
-    rows_to_expand: &Vec<usize>,
-    cols_to_expand: &Vec<usize>,
+    rows_to_expand: &[usize],
+    cols_to_expand: &[usize],
     factor: usize,
 ) -> usize {
     let (x1, y1) = p1;
     let (x2, y2) = p2;
-    let rows_between = number_of_expanded_between(&rows_to_expand, x1, x2) * (factor - 1);
-    let cols_between = number_of_expanded_between(&cols_to_expand, y1, y2) * (factor - 1);
+    let rows_between = number_of_expanded_between(rows_to_expand, x1, x2) * (factor - 1);
+    let cols_between = number_of_expanded_between(cols_to_expand, y1, y2) * (factor - 1);
     x1.abs_diff(x2) + y1.abs_diff(y2) + rows_between + cols_between
 }
 
-fn number_of_expanded_between(expanded: &Vec<usize>, r1: usize, r2: usize) -> usize {
+fn number_of_expanded_between(expanded: &[usize], r1: usize, r2: usize) -> usize {
     let row_min = r1.min(r2);
     let row_max = r1.max(r2);
     expanded
@@ -70,9 +73,9 @@ fn number_of_expanded_between(expanded: &Vec<usize>, r1: usize, r2: usize) -> us
         .len()
 }
 impl Day for Day11 {
-    fn make_day(file: File) -> Self {
+    fn make_day(input: impl Read) -> Self {
         let mut galaxies = Vec::new();
-        std::io::BufReader::new(file)
+        std::io::BufReader::new(input)
             .lines()
             .enumerate()
             .for_each(|(row, line)| {
@@ -85,35 +88,50 @@ impl Day for Day11 {
                         }
                     })
             });
-        Day11 { galaxies }
+        Day11 {
+            galaxies,
+            expansion_factor: 1000000,
+        }
     }
 
-    fn solution1(&self) -> String {
-        let result = self.get_result(2);
-        result.to_string()
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "expansion_factor" => {
+                self.expansion_factor = value.parse().map_err(|_| "should be a number")?;
+                Ok(())
+            }
+            _ => Err(format!("unknown parameter {}", name)),
+        }
     }
 
-    fn solution2(&self) -> String {
-        let result = self.get_result(1000000);
-        result.to_string()
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::grid("image", "[.#]"))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        let size = size.max(2);
+        let mut image = vec![vec!['.'; size]; size];
+        for cell in image.iter_mut().flatten() {
+            if rng.gen_bool(0.04) {
+                *cell = '#';
+            }
+        }
+        // measuring the expansion needs at least two galaxies
+        image[0][rng.gen_range(0..size)] = '#';
+        image[size - 1][rng.gen_range(0..size)] = '#';
+        image
+            .iter()
+            .map(|row| row.iter().collect::<String>() + "\n")
+            .collect()
+    }
 
-    #[test]
-    fn test_day11_sol1() {
-        let input = File::open("./inputs/day11/input_test.txt").expect("File not found");
-        let day = Day11::make_day(input);
-        assert_eq!(day.solution1(), "374");
+    fn solution1(&self) -> String {
+        let result = self.get_result(2);
+        result.to_string()
     }
 
-    #[test]
-    fn test_day11_sol2() {
-        let input = File::open("./inputs/day11/input_test.txt").expect("File not found");
-        let day = Day11::make_day(input);
-        assert_eq!(day.get_result(100), 8410);
+    fn solution2(&self) -> String {
+        let result = self.get_result(self.expansion_factor);
+        result.to_string()
     }
 }
@@ -1,8 +1,11 @@
+use rand::Rng;
 use std::collections::HashMap;
-use std::fs::File;
 use std::io::BufRead;
+use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
+use crate::trace::trace_event;
 
 #[derive(Debug)]
 pub struct Day12 {
@@ -47,7 +50,7 @@ enum BrokenSeqResult<'a> {
     SuccessWithSpringsEnd,
 }
 
-fn form_broken_seq(springs: &[SpringElement], goal: usize) -> BrokenSeqResult {
+fn form_broken_seq(springs: &[SpringElement], goal: usize) -> BrokenSeqResult<'_> {
     for index in 0..goal {
         match springs.get(index) {
             None | Some(SpringElement::Working) => {
@@ -79,7 +82,7 @@ fn find_ways_to_fit_recursive(
     cache: &mut HashMap<CacheKey, usize>,
 ) -> usize {
     while left_springs
-        .get(0)
+        .first()
         .is_some_and(|spring| *spring == SpringElement::Working)
     {
         left_springs = &left_springs[1..];
@@ -101,7 +104,7 @@ fn find_ways_to_fit_recursive(
         return *count;
     }
 
-    let count_with_broken = match form_broken_seq(&left_springs, left_goals[0]) {
+    let count_with_broken = match form_broken_seq(left_springs, left_goals[0]) {
         BrokenSeqResult::FailureToForm => 0,
         BrokenSeqResult::SuccessWithSpringsRemaining(remaining_springs) => {
             find_ways_to_fit_recursive(remaining_springs, &left_goals[1..], cache)
@@ -155,19 +158,63 @@ impl From<char> for SpringElement {
 }
 
 impl Day for Day12 {
-    fn make_day(file: File) -> Self {
-        let lines = std::io::BufReader::new(file)
+    fn make_day(input: impl Read) -> Self {
+        let lines = std::io::BufReader::new(input)
             .lines()
             .map(|line| line.expect("doc should have lines").into())
             .collect();
         Day12 { lines }
     }
 
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::lines("condition record", r"[.#?]+ \d+(,\d+)*"))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // `size` is the length of each of the hundred lines
+        let length = size.max(1);
+        (0..100)
+            .map(|_| {
+                let mut springs = vec![SpringElement::Working; length];
+                let mut position = rng.gen_range(0..length.min(3));
+                while position < length {
+                    let group_length = rng.gen_range(1..=5).min(length - position);
+                    springs[position..position + group_length].fill(SpringElement::Broken);
+                    position += group_length + rng.gen_range(1..=3);
+                }
+                let goal = springs
+                    .split(|spring| *spring == SpringElement::Working)
+                    .filter(|group| !group.is_empty())
+                    .map(|group| group.len().to_string())
+                    .collect::<Vec<_>>()
+                    .join(",");
+                let springs = springs
+                    .iter()
+                    .map(|spring| {
+                        if rng.gen_bool(0.4) {
+                            '?'
+                        } else if *spring == SpringElement::Broken {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>();
+                format!("{} {}\n", springs, goal)
+            })
+            .collect()
+    }
+
     fn solution1(&self) -> String {
         let result = self
             .lines
             .iter()
-            .map(|line| line.find_ways_to_fit())
+            .enumerate()
+            .map(|(index, line)| {
+                let nb_ways = line.find_ways_to_fit();
+                trace_event!("line", number = index + 1, nb_ways = nb_ways);
+                nb_ways
+            })
             .sum::<usize>();
         result.to_string()
     }
@@ -176,7 +223,12 @@ impl Day for Day12 {
         let result = self
             .lines
             .iter()
-            .map(|line| line.find_ways_to_fit_unfold())
+            .enumerate()
+            .map(|(index, line)| {
+                let nb_ways = line.find_ways_to_fit_unfold();
+                trace_event!("line", number = index + 1, nb_ways = nb_ways);
+                nb_ways
+            })
             .sum::<usize>();
         result.to_string()
     }
@@ -186,29 +238,14 @@ impl Day for Day12 {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_day12_sol1() {
-        let input = File::open("./inputs/day12/input_test.txt").expect("File not found");
-        let day = Day12::make_day(input);
-        assert_eq!(day.solution1(), "21");
-    }
-
     #[test]
     fn test_find_ways() {
         let g1: SpringLine = "#.??. 1".to_string().into();
         let mut cache = HashMap::new();
         let v = find_ways_to_fit_recursive(&g1.groups, &g1.goal, &mut cache);
-        dbg!(cache);
         assert_eq!(v, 1);
     }
 
-    #[test]
-    fn test_day12_sol2() {
-        let input = File::open("./inputs/day12/input_test.txt").expect("File not found");
-        let day = Day12::make_day(input);
-        assert_eq!(day.solution2(), "525152");
-    }
-
     #[test]
     fn test_find_ways_2() {
         let g1: SpringLine = "????.#...#... 4,1,1".to_string().into();
@@ -1,7 +1,9 @@
-use std::fs::File;
+use rand::Rng;
 use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+use crate::trace::trace_event;
 
 pub struct Day13 {
     islands: Vec<Island>,
@@ -101,10 +103,76 @@ fn find_symmetry_one_diff(keys: &[LineKey]) -> Option<usize> {
     None
 }
 
+fn generate_island(rng: &mut impl Rng) -> String {
+    // A perfect reflection between two rows, and one between two columns that a single smudge,
+    // in a row left out by the first reflection, keeps from being perfect.
+    let nb_rows = rng.gen_range(5..=15);
+    let nb_cols = rng.gen_range(5..=15);
+    let mut grid = (0..nb_rows)
+        .map(|_| (0..nb_cols).map(|_| rng.gen_bool(0.5)).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    let col_axis = rng.gen_range(0..nb_cols - 1);
+    let col_reach = (col_axis + 1).min(nb_cols - 1 - col_axis);
+    for row in grid.iter_mut() {
+        for offset in 0..col_reach {
+            row[col_axis + 1 + offset] = row[col_axis - offset];
+        }
+    }
+    let row_axis = rng.gen_range(0..=(nb_rows - 3) / 2);
+    for offset in 0..=row_axis {
+        grid[row_axis + 1 + offset] = grid[row_axis - offset].clone();
+    }
+    let smudge_row = rng.gen_range(2 * row_axis + 2..nb_rows);
+    let smudge_col = rng.gen_range(col_axis + 1 - col_reach..=col_axis + col_reach);
+    grid[smudge_row][smudge_col] = !grid[smudge_row][smudge_col];
+
+    if rng.gen_bool(0.5) {
+        grid = (0..nb_cols)
+            .map(|col| grid.iter().map(|row| row[col]).collect())
+            .collect();
+    }
+    grid.iter()
+        .map(|row| {
+            row.iter()
+                .map(|is_rock| if *is_rock { '#' } else { '.' })
+                .collect::<String>()
+                + "\n"
+        })
+        .collect()
+}
+
+fn check_pattern_sizes(lines: &[&str]) -> Vec<Problem> {
+    // rows and columns are compared as the bits of a LineKey
+    let max_size = LineKey::BITS as usize;
+    let mut problems = vec![];
+    let mut nb_rows = 0;
+    for (index, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            nb_rows = 0;
+            continue;
+        }
+        nb_rows += 1;
+        if nb_rows == max_size + 1 {
+            problems.push(Problem::on_line(
+                index + 1,
+                format!("more than {} rows in this pattern", max_size),
+            ));
+        }
+        if line.len() > max_size {
+            problems.push(Problem::on_line(
+                index + 1,
+                format!("more than {} columns", max_size),
+            ));
+        }
+    }
+    problems
+}
+
 impl Day for Day13 {
-    fn make_day(file: File) -> Self {
+    fn make_day(input: impl Read) -> Self {
         let mut contents = String::new();
-        let mut buf_reader = std::io::BufReader::new(file);
+        let mut buf_reader = std::io::BufReader::new(input);
         buf_reader
             .read_to_string(&mut contents)
             .expect("could read");
@@ -115,15 +183,31 @@ impl Day for Day13 {
         Day13 { islands }
     }
 
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(Block::grid("pattern", "[.#]"))
+            .repeat_last_block()
+            .check(check_pattern_sizes)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        (0..size)
+            .map(|_| generate_island(rng))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
     fn solution1(&self) -> String {
         let mut result = 0;
-        for island in self.islands.iter() {
+        for (index, island) in self.islands.iter().enumerate() {
             let horizontal_keys = island.get_horizontal_keys();
             if let Some(t) = find_symmetry(&horizontal_keys) {
+                trace_event!("island", number = index + 1, axis = "row", after = t + 1);
                 result += 100 * (t + 1);
             } else {
                 let vertical_keys = island.get_vertical_keys();
                 if let Some(t) = find_symmetry(&vertical_keys) {
+                    trace_event!("island", number = index + 1, axis = "column", after = t + 1);
                     result += t + 1;
                 } else {
                     panic!("no sym");
@@ -135,13 +219,15 @@ impl Day for Day13 {
 
     fn solution2(&self) -> String {
         let mut result = 0;
-        for island in self.islands.iter() {
+        for (index, island) in self.islands.iter().enumerate() {
             let horizontal_keys = island.get_horizontal_keys();
             if let Some(t) = find_symmetry_one_diff(&horizontal_keys) {
+                trace_event!("island", number = index + 1, axis = "row", after = t + 1);
                 result += 100 * (t + 1);
             } else {
                 let vertical_keys = island.get_vertical_keys();
                 if let Some(t) = find_symmetry_one_diff(&vertical_keys) {
+                    trace_event!("island", number = index + 1, axis = "column", after = t + 1);
                     result += t + 1;
                 } else {
                     panic!("no sym one diff");
@@ -151,22 +237,3 @@ impl Day for Day13 {
         result.to_string()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day13_sol1() {
-        let input = File::open("./inputs/day13/input_test.txt").expect("File not found");
-        let day = Day13::make_day(input);
-        assert_eq!(day.solution1(), "405");
-    }
-
-    #[test]
-    fn test_day13_sol2() {
-        let input = File::open("./inputs/day13/input_test.txt").expect("File not found");
-        let day = Day13::make_day(input);
-        assert_eq!(day.solution2(), "400");
-    }
-}
@@ -1,28 +1,26 @@
 use itertools::Either;
 use ndarray::{Array2, Axis};
+use rand::Rng;
 use std::collections::HashMap;
-use std::fs::File;
 use std::io::BufRead;
+use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
+use crate::numeric::{self, Overflow};
 
 pub struct Day14 {
     tiles: Array2<Tile>,
 }
 
-#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash)]
+#[derive(Debug, Eq, PartialEq, Copy, Clone, Hash, Default)]
 enum Tile {
+    #[default]
     Empty,
     Square,
     Circle,
 }
 
-impl Default for Tile {
-    fn default() -> Self {
-        Tile::Empty
-    }
-}
-
 impl From<char> for Tile {
     fn from(value: char) -> Self {
         match value {
@@ -66,15 +64,17 @@ fn tilt(tiles: &Array2<Tile>, axis_number: usize, reversed: bool) -> Array2<Tile
     tilted
 }
 
-fn get_load(tiles: &Array2<Tile>) -> usize {
-    tiles
-        .axis_iter(Axis(0))
-        .rev()
-        .enumerate()
-        .map(|(load_min_one, row)| {
-            row.iter().filter(|tile| **tile == Tile::Circle).count() * (load_min_one + 1)
-        })
-        .sum::<usize>()
+fn get_load(tiles: &Array2<Tile>) -> Result<i64, Overflow> {
+    numeric::sum(
+        tiles
+            .axis_iter(Axis(0))
+            .rev()
+            .enumerate()
+            .map(|(load_min_one, row)| {
+                let nb_rocks = row.iter().filter(|tile| **tile == Tile::Circle).count();
+                numeric::mul(&(nb_rocks as i64), &(load_min_one as i64 + 1))
+            }),
+    )
 }
 
 fn tilt_four_times(tiles: Array2<Tile>) -> Array2<Tile> {
@@ -86,8 +86,8 @@ fn tilt_four_times(tiles: Array2<Tile>) -> Array2<Tile> {
 }
 
 impl Day for Day14 {
-    fn make_day(file: File) -> Self {
-        let data = std::io::BufReader::new(file)
+    fn make_day(input: impl Read) -> Self {
+        let data = std::io::BufReader::new(input)
             .lines()
             .map(|line| {
                 line.expect("doc should have lines")
@@ -103,10 +103,29 @@ impl Day for Day14 {
         Day14 { tiles }
     }
 
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::grid("platform", "[.#O]"))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        let size = size.max(1);
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| match rng.gen_range(0..100) {
+                        0..=19 => 'O',
+                        20..=34 => '#',
+                        _ => '.',
+                    })
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+
     fn solution1(&self) -> String {
         let tilted = tilt(&self.tiles, 1, false);
-        let result = get_load(&tilted);
-        result.to_string()
+        numeric::answer(get_load(&tilted))
     }
 
     fn solution2(&self) -> String {
@@ -131,26 +150,6 @@ impl Day for Day14 {
             vec_seen_tiles.push(curr_tilted.clone());
         }
         let same_tiles_step = ((1000000000 - current_step) % period) + (current_step - period);
-        let result = get_load(&vec_seen_tiles[same_tiles_step]);
-        result.to_string()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day14_sol1() {
-        let input = File::open("./inputs/day14/input_test.txt").expect("File not found");
-        let day = Day14::make_day(input);
-        assert_eq!(day.solution1(), "136");
-    }
-
-    #[test]
-    fn test_day14_sol2() {
-        let input = File::open("./inputs/day14/input_test.txt").expect("File not found");
-        let day = Day14::make_day(input);
-        assert_eq!(day.solution2(), "64");
+        numeric::answer(get_load(&vec_seen_tiles[same_tiles_step]))
     }
 }
@@ -1,7 +1,8 @@
-use std::fs::File;
+use rand::Rng;
 use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
 
 type Num = u32;
 
@@ -24,7 +25,7 @@ impl Instruction {
     fn get_label(&self) -> &str {
         match self {
             Instruction::Put(lens) => &lens.label,
-            Instruction::Remove { label } => &label,
+            Instruction::Remove { label } => label,
         }
     }
 }
@@ -50,9 +51,9 @@ fn hash_algorithm(s: &str) -> Num {
 }
 
 impl Day for Day15 {
-    fn make_day(file: File) -> Self {
+    fn make_day(input: impl Read) -> Self {
         let mut contents = String::new();
-        let mut buf_reader = std::io::BufReader::new(file);
+        let mut buf_reader = std::io::BufReader::new(input);
         buf_reader
             .read_to_string(&mut contents)
             .expect("could read");
@@ -64,6 +65,35 @@ impl Day for Day15 {
         Day15 { steps }
     }
 
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::starting_with(
+            "initialization sequence",
+            "[a-z]+(=[1-9]|-)(,[a-z]+(=[1-9]|-))*",
+        ))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // labels come back often, so that lenses get replaced and removed
+        let labels = (0..(size / 4).max(1))
+            .map(|_| {
+                (0..rng.gen_range(2..=6))
+                    .map(|_| char::from(b'a' + rng.gen_range(0..26)))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let steps = (0..size.max(1))
+            .map(|_| {
+                let label = &labels[rng.gen_range(0..labels.len())];
+                if rng.gen_bool(0.3) {
+                    format!("{}-", label)
+                } else {
+                    format!("{}={}", label, rng.gen_range(1..=9))
+                }
+            })
+            .collect::<Vec<_>>();
+        steps.join(",") + "\n"
+    }
+
     fn solution1(&self) -> String {
         let result = self
             .steps
@@ -115,20 +145,6 @@ impl Day for Day15 {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_day15_sol1() {
-        let input = File::open("./inputs/day15/input_test.txt").expect("File not found");
-        let day = Day15::make_day(input);
-        assert_eq!(day.solution1(), "1320");
-    }
-
-    #[test]
-    fn test_day15_sol2() {
-        let input = File::open("./inputs/day15/input_test.txt").expect("File not found");
-        let day = Day15::make_day(input);
-        assert_eq!(day.solution2(), "145");
-    }
-
     #[test]
     fn test_hash() {
         assert_eq!(hash_algorithm("HASH"), 52);
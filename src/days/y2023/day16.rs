@@ -1,11 +1,13 @@
+use rand::Rng;
 use std::cmp::max;
-use std::fs::File;
 use std::io::BufRead;
+use std::io::Read;
 
 use ndarray::Array2;
 use rayon::prelude::*;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
 
 type Coordinate = (isize, isize);
 
@@ -16,7 +18,7 @@ pub struct Day16 {
 impl Day16 {
     fn compute_energized_cells(&self, start_ray: Ray) -> usize {
         let (nb_rows, nb_cols) = self.contraptions.dim();
-        let (nb_rows, nb_cols) = (nb_rows as usize, nb_cols as usize);
+        let (nb_rows, nb_cols) = (nb_rows, nb_cols);
         let mut rays_cache = Array2::default(self.contraptions.dim());
         let mut energized = Array2::default(self.contraptions.dim());
         let mut current_rays = vec![start_ray];
@@ -164,13 +166,13 @@ fn add_if_not_in_cache(ray: Ray, cache: &mut Array2<Vec<Direction>>, new_rays: &
 }
 
 impl Day for Day16 {
-    fn make_day(file: File) -> Self {
-        let data: Vec<Vec<Option<Contraption>>> = std::io::BufReader::new(file)
+    fn make_day(input: impl Read) -> Self {
+        let data: Vec<Vec<Option<Contraption>>> = std::io::BufReader::new(input)
             .lines()
             .map(|line| {
                 line.expect("doc should have lines")
                     .chars()
-                    .map(|ch| Contraption::get_from_char(ch))
+                    .map(Contraption::get_from_char)
                     .collect()
             })
             .collect();
@@ -181,6 +183,28 @@ impl Day for Day16 {
         Day16 { contraptions }
     }
 
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::grid("contraption", r"[.|\-/\\]"))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        let size = size.max(1);
+        (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| {
+                        if rng.gen_bool(0.1) {
+                            ['|', '-', '\\', '/'][rng.gen_range(0..4)]
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+
     fn solution1(&self) -> String {
         self.compute_energized_cells(Ray {
             direction: Direction::Right,
@@ -191,7 +215,7 @@ impl Day for Day16 {
 
     fn solution2(&self) -> String {
         let (nb_rows, nb_cols) = self.contraptions.dim();
-        let (nb_rows, nb_cols) = (nb_rows as usize, nb_cols as usize);
+        let (nb_rows, nb_cols) = (nb_rows, nb_cols);
         let energized_rows = (0..nb_rows)
             .into_par_iter()
             .map(|row| {
@@ -227,22 +251,3 @@ impl Day for Day16 {
         max(energized_cols, energized_rows).to_string()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day16_sol1() {
-        let input = File::open("./inputs/day16/input_test.txt").expect("File not found");
-        let day = Day16::make_day(input);
-        assert_eq!(day.solution1(), "46");
-    }
-
-    #[test]
-    fn test_day16_sol2() {
-        let input = File::open("./inputs/day16/input_test.txt").expect("File not found");
-        let day = Day16::make_day(input);
-        assert_eq!(day.solution2(), "51");
-    }
-}
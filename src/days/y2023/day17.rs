@@ -1,9 +1,11 @@
 use ndarray::Array2;
 use pathfinding::prelude::astar;
-use std::fs::File;
+use rand::Rng;
 use std::io::BufRead;
+use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
 
 type Coordinate = (usize, usize);
 type Heat = u32;
@@ -188,7 +190,7 @@ impl Node2 {
                     .get_range_pos(self.pos, nb_rows, nb_cols)
                     .iter()
                     .map(|pos| {
-                        let heat_consumed = heat_from(self.pos, *pos, &heat_map);
+                        let heat_consumed = heat_from(self.pos, *pos, heat_map);
                         (
                             Node2 {
                                 pos: *pos,
@@ -203,8 +205,8 @@ impl Node2 {
     }
 }
 impl Day for Day17 {
-    fn make_day(file: File) -> Self {
-        let data: Vec<Vec<_>> = std::io::BufReader::new(file)
+    fn make_day(input: impl Read) -> Self {
+        let data: Vec<Vec<_>> = std::io::BufReader::new(input)
             .lines()
             .map(|line| {
                 line.expect("doc should have lines")
@@ -220,6 +222,25 @@ impl Day for Day17 {
         Day17 { heat_map }
     }
 
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::grid("heat loss map", "[1-9]"))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // ultra crucibles need at least four blocks to reach the bottom right corner, and the map
+        // is up to twice as wide as it is high
+        let nb_rows = size.max(5);
+        let nb_cols = rng.gen_range(nb_rows..=2 * nb_rows);
+        (0..nb_rows)
+            .map(|_| {
+                (0..nb_cols)
+                    .map(|_| char::from(b'0' + rng.gen_range(1..=9)))
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+
     fn solution1(&self) -> String {
         let (nb_rows, nb_cols) = self.heat_map.dim();
         let start_node = Node {
@@ -255,22 +276,3 @@ impl Day for Day17 {
         result.to_string()
     }
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_day17_sol1() {
-        let input = File::open("./inputs/day17/input_test.txt").expect("File not found");
-        let day = Day17::make_day(input);
-        assert_eq!(day.solution1(), "102");
-    }
-
-    #[test]
-    fn test_day17_sol2() {
-        let input = File::open("./inputs/day17/input_test.txt").expect("File not found");
-        let day = Day17::make_day(input);
-        assert_eq!(day.solution2(), "94");
-    }
-}
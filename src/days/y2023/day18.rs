@@ -0,0 +1,283 @@
+use rand::Rng;
+use std::io::BufRead;
+use std::io::Read;
+
+use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+use crate::numeric::{self, Number, Overflow, Precision};
+
+type Num = i64;
+type Coordinate = (Num, Num);
+
+pub struct Day18 {
+    dig_instructions: Vec<DigInstruction>,
+    dig_instructions_alternate: Vec<DigInstruction>,
+    precision: Precision,
+}
+
+impl Day18 {
+    fn compute_inside_points<N: Number>(&self, alternate: bool) -> Result<N, Overflow> {
+        let mut current_vertice = (N::from(0), N::from(0));
+        let mut double_area = N::from(0);
+        let mut boundary_points = N::from(0);
+        let iter = if alternate {
+            self.dig_instructions_alternate.iter()
+        } else {
+            self.dig_instructions.iter()
+        };
+        for dig_instruction in iter {
+            let (dr, dc) = dig_instruction.direction.get_movement();
+            let nb_digs = N::from(dig_instruction.nb_digs);
+            current_vertice = (
+                numeric::add(&current_vertice.0, &numeric::mul(&N::from(dr), &nb_digs)?)?,
+                numeric::add(&current_vertice.1, &numeric::mul(&N::from(dc), &nb_digs)?)?,
+            );
+            boundary_points = numeric::add(&boundary_points, &nb_digs)?;
+            let diff_area = if dr == 0 {
+                numeric::mul(&numeric::mul(&current_vertice.0, &nb_digs)?, &N::from(dc))?
+            } else {
+                numeric::mul(&numeric::mul(&current_vertice.1, &nb_digs)?, &N::from(-dr))?
+            };
+            double_area = numeric::add(&double_area, &diff_area)?;
+        }
+        if double_area < N::from(0) {
+            double_area = numeric::sub(&N::from(0), &double_area)?;
+        }
+        numeric::add(
+            &numeric::add(
+                &double_area.div_floor(&N::from(2)),
+                &boundary_points.div_floor(&N::from(2)),
+            )?,
+            &N::from(1),
+        )
+    }
+}
+#[derive(Debug)]
+struct DigInstruction {
+    direction: Direction,
+    nb_digs: Num,
+}
+
+impl DigInstruction {
+    fn get_two_instructions_from_line(value: &str) -> (Self, Self) {
+        let elements = value.split_whitespace().collect::<Vec<_>>();
+        let direction = elements[0].chars().next().unwrap().into();
+        let nb_digs = elements[1].parse().unwrap();
+        let instr1 = DigInstruction { direction, nb_digs };
+        let dir_alternate = match elements[2].chars().nth(7).unwrap() {
+            '0' => Direction::Right,
+            '1' => Direction::Down,
+            '2' => Direction::Left,
+            '3' => Direction::Up,
+            _ => panic!("wrong digit"),
+        };
+        let nb_digs_alternate = Num::from_str_radix(&elements[2][2..7], 16).unwrap();
+        let instr2 = DigInstruction {
+            direction: dir_alternate,
+            nb_digs: nb_digs_alternate,
+        };
+        (instr1, instr2)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+enum Direction {
+    Up,
+    Left,
+    Down,
+    Right,
+}
+
+impl Direction {
+    fn get_movement(&self) -> Coordinate {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Left => (0, -1),
+            Direction::Down => (1, 0),
+            Direction::Right => (0, 1),
+        }
+    }
+}
+
+impl From<char> for Direction {
+    fn from(value: char) -> Self {
+        match value {
+            'U' => Direction::Up,
+            'L' => Direction::Left,
+            'D' => Direction::Down,
+            'R' => Direction::Right,
+            _ => panic!("unexpected direction char"),
+        }
+    }
+}
+
+fn generate_histogram_plan(
+    rng: &mut impl Rng,
+    nb_columns: usize,
+    max_length: Num,
+) -> Vec<(Direction, Num)> {
+    let max_length = max_length.max(2);
+    let mut height = rng.gen_range(1..=max_length);
+    let mut width = 0;
+    let mut plan = vec![(Direction::Up, height)];
+    for column in 0..nb_columns {
+        let column_width = rng.gen_range(1..=max_length);
+        plan.push((Direction::Right, column_width));
+        width += column_width;
+        if column + 1 < nb_columns {
+            let new_height = loop {
+                let new_height = rng.gen_range(1..=max_length);
+                if new_height != height {
+                    break new_height;
+                }
+            };
+            if new_height > height {
+                plan.push((Direction::Up, new_height - height));
+            } else {
+                plan.push((Direction::Down, height - new_height));
+            }
+            height = new_height;
+        }
+    }
+    plan.push((Direction::Down, height));
+    plan.push((Direction::Left, width));
+    plan
+}
+
+fn check_loops(lines: &[&str]) -> Vec<Problem> {
+    // both dig plans have to come back to where they started
+    let mut ends = [(0, 0); 2];
+    for line in lines {
+        let (instruction, instruction_alternate) =
+            DigInstruction::get_two_instructions_from_line(line);
+        for (end, instruction) in ends.iter_mut().zip([instruction, instruction_alternate]) {
+            let (dr, dc) = instruction.direction.get_movement();
+            *end = (
+                end.0 + dr * instruction.nb_digs,
+                end.1 + dc * instruction.nb_digs,
+            );
+        }
+    }
+    ends.iter()
+        .enumerate()
+        .filter(|(_, end)| **end != (0, 0))
+        .map(|(part, end)| {
+            Problem::on_input(format!(
+                "the plan of part {} ends at {:?} instead of its start",
+                part + 1,
+                end
+            ))
+        })
+        .collect()
+}
+
+impl Day for Day18 {
+    fn make_day(input: impl Read) -> Self {
+        let (dig_instructions, dig_instructions_alternate) = std::io::BufReader::new(input)
+            .lines()
+            .map(|line| {
+                DigInstruction::get_two_instructions_from_line(
+                    line.expect("doc should have lines").as_str(),
+                )
+            })
+            .unzip();
+        Day18 {
+            dig_instructions,
+            dig_instructions_alternate,
+            precision: Precision::default(),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "bigint" => {
+                self.precision = Precision::from_param(value)?;
+                Ok(())
+            }
+            _ => Err(format!("unknown parameter {}", name)),
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(Block::lines(
+                "dig instruction",
+                r"[UDLR] \d+ \(#[0-9a-f]{5}[0-3]\)",
+            ))
+            .check(check_loops)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // both plans are histograms of the same number of columns, in a random orientation
+        let nb_columns = (size / 2).max(1);
+        let plan = generate_histogram_plan(rng, nb_columns, 10);
+        let plan_alternate = generate_histogram_plan(rng, nb_columns, 0xfffff / nb_columns as Num);
+        let quarter_turns = rng.gen_range(0..4);
+        let turn = |direction: Direction| {
+            (0..quarter_turns).fold(direction, |direction, _| match direction {
+                Direction::Up => Direction::Right,
+                Direction::Right => Direction::Down,
+                Direction::Down => Direction::Left,
+                Direction::Left => Direction::Up,
+            })
+        };
+        plan.into_iter()
+            .zip(plan_alternate)
+            .map(
+                |((direction, nb_digs), (direction_alternate, nb_digs_alternate))| {
+                    let direction_char = match turn(direction) {
+                        Direction::Up => 'U',
+                        Direction::Left => 'L',
+                        Direction::Down => 'D',
+                        Direction::Right => 'R',
+                    };
+                    let direction_digit = match turn(direction_alternate) {
+                        Direction::Right => 0,
+                        Direction::Down => 1,
+                        Direction::Left => 2,
+                        Direction::Up => 3,
+                    };
+                    format!(
+                        "{} {} (#{:05x}{})\n",
+                        direction_char, nb_digs, nb_digs_alternate, direction_digit
+                    )
+                },
+            )
+            .collect()
+    }
+
+    fn solution1(&self) -> String {
+        numeric::solve(
+            self.precision,
+            || self.compute_inside_points(false),
+            || self.compute_inside_points(false),
+        )
+    }
+
+    fn solution2(&self) -> String {
+        numeric::solve(
+            self.precision,
+            || self.compute_inside_points(true),
+            || self.compute_inside_points(true),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bigint() {
+        let side = 4_000_000_000u64;
+        let input = format!(
+            "R {side} (#000010)\nD {side} (#000011)\nL {side} (#000012)\nU {side} (#000013)\n"
+        );
+        let mut day = Day18::make_day(input.as_bytes());
+        assert_eq!(day.solution2(), "4");
+        let overflow = std::panic::catch_unwind(|| day.solution1());
+        assert!(crate::panic_message(overflow.unwrap_err()).contains("--bigint"));
+        day.set_param("bigint", "true").unwrap();
+        assert_eq!(day.solution1(), ((side as u128 + 1).pow(2)).to_string());
+    }
+}
@@ -1,11 +1,16 @@
 use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::Rng;
 use std::cmp::{max, min};
 use std::collections::HashMap;
-use std::fs::File;
+use std::collections::HashSet;
+use std::fmt;
 use std::io::Read;
 use std::ops::Range;
 
 use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+use crate::trace::{self, trace_event};
 
 type Num = u64;
 
@@ -18,14 +23,30 @@ pub struct Day19 {
 impl Day19 {
     fn does_accept(&self, part: &Part) -> bool {
         let mut next_workflow_id = "in".to_string();
+        let mut path = vec![];
         loop {
+            if trace::is_enabled() {
+                path.push(next_workflow_id.clone());
+            }
             let next_workflow = self.workflows.get(&next_workflow_id).unwrap();
-            let result = next_workflow.apply_to(&part);
+            let result = next_workflow.apply_to(part);
             match result {
                 RuleResult::Accepted => {
+                    trace_event!(
+                        "part",
+                        rating = part,
+                        path = path.join(" -> "),
+                        result = "A"
+                    );
                     return true;
                 }
                 RuleResult::Rejected => {
+                    trace_event!(
+                        "part",
+                        rating = part,
+                        path = path.join(" -> "),
+                        result = "R"
+                    );
                     return false;
                 }
                 RuleResult::Workflow(new_id) => {
@@ -63,7 +84,7 @@ impl Day19 {
                     }
                     FollowUp::Id(id) => {
                         let mut additional_conditions =
-                            find_conditions_id(&id, &conditions_by_id, cache);
+                            find_conditions_id(&id, conditions_by_id, cache);
                         let to_add = accepting_conditions.conditions.clone();
                         for additional_condition in additional_conditions.iter_mut() {
                             additional_condition.append(&mut to_add.clone());
@@ -118,7 +139,7 @@ impl Day19 {
         result
     }
 }
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 struct Workflow {
     id: String,
     rules: Vec<Rule>,
@@ -128,12 +149,12 @@ struct Workflow {
 impl Workflow {
     fn apply_to(&self, part: &Part) -> RuleResult {
         for rule in self.rules.iter() {
-            let result = rule.apply_to(&part);
+            let result = rule.apply_to(part);
             if let ActualResult::Rule(rule_result) = result {
                 return rule_result;
             }
         }
-        return self.default_result.clone();
+        self.default_result.clone()
     }
 
     fn find_conditions_for_accepting(&self) -> Vec<AcceptingConditionsWithFollowUp> {
@@ -219,7 +240,17 @@ impl From<&str> for Workflow {
     }
 }
 
-#[derive(Debug)]
+impl fmt::Display for Workflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{{", self.id)?;
+        for rule in self.rules.iter() {
+            write!(f, "{},", rule)?;
+        }
+        write!(f, "{}}}", self.default_result)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 struct Rule {
     rule_kind: RuleKind,
     compared_to: Num,
@@ -273,7 +304,27 @@ impl From<&str> for Rule {
     }
 }
 
-#[derive(Debug)]
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let category = match self.category {
+            Category::X => 'x',
+            Category::M => 'm',
+            Category::A => 'a',
+            Category::S => 's',
+        };
+        let rule_kind = match self.rule_kind {
+            RuleKind::GreaterThan => '>',
+            RuleKind::SmallerThan => '<',
+        };
+        write!(
+            f,
+            "{}{}{}:{}",
+            category, rule_kind, self.compared_to, self.result
+        )
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 enum RuleKind {
     GreaterThan,
     SmallerThan,
@@ -303,7 +354,7 @@ enum Category {
     S,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum RuleResult {
     Accepted,
     Rejected,
@@ -325,7 +376,17 @@ impl From<&str> for RuleResult {
     }
 }
 
-#[derive(Debug)]
+impl fmt::Display for RuleResult {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RuleResult::Accepted => write!(f, "A"),
+            RuleResult::Rejected => write!(f, "R"),
+            RuleResult::Workflow(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
 struct Part {
     x: Num,
     m: Num,
@@ -373,6 +434,12 @@ impl From<&str> for Part {
     }
 }
 
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{{x={},m={},a={},s={}}}", self.x, self.m, self.a, self.s)
+    }
+}
+
 #[derive(Debug)]
 struct AcceptingConditionsWithFollowUp {
     conditions: Vec<ElementaryCondition>,
@@ -400,10 +467,50 @@ enum ComparisonType {
     SmallerEqual,
 }
 
+fn check_workflows(lines: &[&str]) -> Vec<Problem> {
+    let workflows = lines
+        .iter()
+        .take_while(|line| !line.is_empty())
+        .enumerate()
+        .map(|(index, line)| (index + 1, Workflow::from(*line)))
+        .collect::<Vec<_>>();
+    let mut problems = vec![];
+    let mut definitions = HashMap::with_capacity(workflows.len());
+    for (line, workflow) in workflows.iter() {
+        if let Some(first_line) = definitions.insert(workflow.id.as_str(), *line) {
+            problems.push(Problem::on_line(
+                *line,
+                format!(
+                    "workflow {} already defined on line {}",
+                    workflow.id, first_line
+                ),
+            ));
+        }
+    }
+    if !definitions.contains_key("in") {
+        problems.push(Problem::on_input("no workflow named in"));
+    }
+    for (line, workflow) in workflows.iter() {
+        let results = workflow
+            .rules
+            .iter()
+            .map(|rule| &rule.result)
+            .chain([&workflow.default_result]);
+        for result in results {
+            if let RuleResult::Workflow(id) = result {
+                if !definitions.contains_key(id.as_str()) {
+                    problems.push(Problem::on_line(*line, format!("unknown workflow {}", id)));
+                }
+            }
+        }
+    }
+    problems
+}
+
 impl Day for Day19 {
-    fn make_day(file: File) -> Self {
+    fn make_day(input: impl Read) -> Self {
         let mut contents = String::new();
-        let mut buf_reader = std::io::BufReader::new(file);
+        let mut buf_reader = std::io::BufReader::new(input);
         buf_reader
             .read_to_string(&mut contents)
             .expect("could read");
@@ -420,11 +527,74 @@ impl Day for Day19 {
         Day19 { workflows, parts }
     }
 
+    fn input_spec() -> Spec {
+        let result = "[a-z]+|A|R";
+        Spec::default()
+            .block(Block::lines(
+                "workflow",
+                &format!(r"[a-z]+\{{([xmas][<>]\d+:({}),)*({})\}}", result, result),
+            ))
+            .block(Block::lines("part", r"\{x=\d+,m=\d+,a=\d+,s=\d+\}"))
+            .check(check_workflows)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // workflows form a tree under `in`, so that every part ends up accepted or rejected
+        let nb_workflows = size.max(1);
+        let mut names = vec!["in".to_string()];
+        let mut seen_names = HashSet::from(["in".to_string()]);
+        while names.len() < nb_workflows {
+            let name = (0..rng.gen_range(2..=4))
+                .map(|_| char::from(b'a' + rng.gen_range(0..26)))
+                .collect::<String>();
+            if seen_names.insert(name.clone()) {
+                names.push(name);
+            }
+        }
+        let mut results = vec![vec![]; nb_workflows];
+        for child in 1..nb_workflows {
+            results[rng.gen_range(0..child)].push(names[child].clone());
+        }
+
+        let mut workflow_lines = names
+            .iter()
+            .zip(results)
+            .map(|(name, mut workflow_results)| {
+                for _ in 0..rng.gen_range(usize::from(workflow_results.is_empty())..=2) {
+                    let result = if rng.gen_bool(0.5) { "A" } else { "R" };
+                    workflow_results.push(result.to_string());
+                }
+                workflow_results.shuffle(rng);
+                let default_result = workflow_results.pop().unwrap();
+                let mut rules = workflow_results
+                    .into_iter()
+                    .map(|result| {
+                        let category = ['x', 'm', 'a', 's'][rng.gen_range(0..4)];
+                        let comparison = if rng.gen_bool(0.5) { '<' } else { '>' };
+                        let compared_to = rng.gen_range(1..=4000);
+                        format!("{}{}{}:{}", category, comparison, compared_to, result)
+                    })
+                    .collect::<Vec<_>>();
+                rules.push(default_result);
+                format!("{}{{{}}}\n", name, rules.join(","))
+            })
+            .collect::<Vec<_>>();
+        workflow_lines.shuffle(rng);
+
+        let parts = (0..nb_workflows)
+            .map(|_| {
+                let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+                format!("{}\n", Part { x, m, a, s })
+            })
+            .collect::<String>();
+        workflow_lines.concat() + "\n" + &parts
+    }
+
     fn solution1(&self) -> String {
         let result = self
             .parts
             .iter()
-            .filter(|part| self.does_accept(*part))
+            .filter(|part| self.does_accept(part))
             .map(|part| part.get_score())
             .sum::<Num>();
         result.to_string()
@@ -438,25 +608,23 @@ impl Day for Day19 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::check_round_trip;
+    use std::fs::File;
 
     #[test]
-    fn test_day19_sol1() {
-        let input = File::open("./inputs/day19/input_test.txt").expect("File not found");
-        let day = Day19::make_day(input);
-        assert_eq!(day.solution1(), "19114");
-    }
-
-    #[test]
-    fn test_day19_sol2() {
-        let input = File::open("./inputs/day19/input_test.txt").expect("File not found");
+    fn test_find_conditions_for_accepting() {
+        let input = File::open("./fixtures/day19/example.txt").expect("File not found");
         let day = Day19::make_day(input);
-        assert_eq!(day.solution2(), "167409079868000");
+        assert_eq!(day.find_numbers_of_accepting(), 167409079868000);
     }
 
     #[test]
-    fn test_find_conditions_for_accepting() {
-        let input = File::open("./inputs/day19/input_test.txt").expect("File not found");
-        let day = Day19::make_day(input);
-        dbg!(day.find_numbers_of_accepting());
+    fn test_display_round_trip() {
+        check_round_trip::<Day19, Workflow>(10, true, |line| {
+            (!line.starts_with('{')).then(|| Workflow::from(line))
+        });
+        check_round_trip::<Day19, Part>(10, true, |line| {
+            line.starts_with('{').then(|| Part::from(line))
+        });
     }
 }
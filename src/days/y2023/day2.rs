@@ -0,0 +1,501 @@
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::cmp::max;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::io::BufRead;
+use std::io::Read;
+use std::str::FromStr;
+
+use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+
+#[derive(Debug)]
+pub struct Day2 {
+    games: Vec<Game>,
+    /// Every color of the input.
+    colors: Vec<String>,
+    bag: Bag,
+}
+
+/// Numbers of cubes by color, the missing colors having none.
+pub type Bag = BTreeMap<String, u32>;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Game {
+    grabs: Vec<CubeGrab>,
+    id: u32,
+}
+
+impl Game {
+    fn get_minimum_dice_bag(&self) -> Bag {
+        let mut dice_bag = Bag::new();
+        for grab in self.grabs.iter() {
+            for (color, grab_amount) in grab.nb_cubes_by_color.iter() {
+                let dice_bag_amount = dice_bag.entry(color.clone()).or_insert(0);
+                *dice_bag_amount = max(*dice_bag_amount, *grab_amount);
+            }
+        }
+        dice_bag
+    }
+
+    /// The product of the cubes of each color in the minimum bag, a color the game never shows
+    /// making it zero.
+    fn get_power(&self, colors: &[String]) -> u32 {
+        let dice_bag = self.get_minimum_dice_bag();
+        colors
+            .iter()
+            .map(|color| dice_bag.get(color).copied().unwrap_or(0))
+            .product()
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct CubeGrab {
+    nb_cubes_by_color: Bag,
+}
+
+impl CubeGrab {
+    fn is_acceptable(&self, bag: &Bag) -> bool {
+        self.nb_cubes_by_color
+            .iter()
+            .all(|(color, nb)| bag.get(color).is_some_and(|in_bag| in_bag >= nb))
+    }
+}
+
+const COLOR_NAMES: [&str; 3] = ["red", "green", "blue"];
+
+/// Reads a bag written `color:count,color:count...`.
+pub fn parse_bag(text: &str) -> Result<Bag, String> {
+    text.split(',')
+        .map(|entry| {
+            let (color, count) = entry
+                .split_once(':')
+                .ok_or_else(|| format!("{} should be color:count", entry))?;
+            let count = count
+                .parse()
+                .map_err(|_| format!("{} should be a number of cubes", count))?;
+            Ok((color.to_string(), count))
+        })
+        .collect()
+}
+
+impl FromStr for Game {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, grabs) = s
+            .split_once(':')
+            .ok_or_else(|| format!("{} should be Game <id>: <grabs>", s))?;
+        let id = match name.split_whitespace().collect::<Vec<_>>()[..] {
+            ["Game", id] => id
+                .parse()
+                .map_err(|_| format!("{} should be a game id", id))?,
+            _ => return Err(format!("{} should be Game <id>", name)),
+        };
+        let grabs = grabs
+            .split(';')
+            .map(|grab| grab.parse())
+            .collect::<Result<_, _>>()?;
+        Ok(Game { grabs, id })
+    }
+}
+
+impl FromStr for CubeGrab {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colors_split = s.split(',');
+        let mut nb_cubes_by_color = Bag::new();
+        for color in colors_split {
+            let mut nb_split = color.split_whitespace();
+            let nb = nb_split
+                .next()
+                .and_then(|nb| nb.parse().ok())
+                .ok_or("missing count")?;
+            let color = nb_split.next().ok_or("missing color")?;
+            if let Some(extra) = nb_split.next() {
+                return Err(format!("unexpected {} after {} {}", extra, nb, color));
+            }
+            if nb_cubes_by_color.insert(color.to_string(), nb).is_some() {
+                return Err(format!("{} is counted twice in a grab", color));
+            }
+        }
+        let result = CubeGrab { nb_cubes_by_color };
+        Ok(result)
+    }
+}
+
+fn check_games(lines: &[&str]) -> Vec<Problem> {
+    // the shape of the lines is checked by the spec, leaving the colors counted twice
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let error = line.parse::<Game>().err()?;
+            Some(Problem::on_line(index + 1, error))
+        })
+        .collect()
+}
+
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (idx, grab) in self.grabs.iter().enumerate() {
+            if idx > 0 {
+                write!(f, "; ")?;
+            }
+            write!(f, "{}", grab)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CubeGrab {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let colors = self
+            .nb_cubes_by_color
+            .iter()
+            .map(|(color, nb)| format!("{} {}", nb, color))
+            .collect::<Vec<_>>();
+        write!(f, "{}", colors.join(", "))
+    }
+}
+
+pub fn format_bag(bag: &Bag) -> String {
+    bag.iter()
+        .map(|(color, count)| format!("{}:{}", color, count))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// The number of ways to choose `k` items out of `n`.
+fn binomial(n: u32, k: u32) -> f64 {
+    if k > n {
+        return 0.;
+    }
+    (1..=k.min(n - k)).fold(1., |ways, i| ways * (n - i + 1) as f64 / i as f64)
+}
+
+impl CubeGrab {
+    /// The probability of drawing exactly these cubes at once out of the bag, without putting
+    /// them back.
+    fn probability(&self, bag: &Bag) -> f64 {
+        let nb_in_bag = bag.values().sum();
+        let nb_drawn = self.nb_cubes_by_color.values().sum();
+        if nb_drawn > nb_in_bag {
+            return 0.;
+        }
+        let ways_to_draw_colors = self
+            .nb_cubes_by_color
+            .iter()
+            .map(|(color, nb)| binomial(bag.get(color).copied().unwrap_or(0), *nb))
+            .product::<f64>();
+        ways_to_draw_colors / binomial(nb_in_bag, nb_drawn)
+    }
+}
+
+impl Day2 {
+    /// The smallest bag every game could have been played with.
+    pub fn minimal_bag(&self) -> Bag {
+        self.minimal_bag_of(self.games.iter())
+    }
+
+    fn minimal_bag_of<'a>(&self, games: impl Iterator<Item = &'a Game>) -> Bag {
+        let mut bag = self
+            .colors
+            .iter()
+            .map(|color| (color.clone(), 0))
+            .collect::<Bag>();
+        for game in games {
+            for (color, nb) in game.get_minimum_dice_bag() {
+                let in_bag = bag.entry(color).or_insert(0);
+                *in_bag = max(*in_bag, nb);
+            }
+        }
+        bag
+    }
+
+    /// Every bag of the colors of the input holding at most `budget` cubes with which all the
+    /// given games are possible.
+    pub fn bags_admitting(&self, game_ids: &[u32], budget: u32) -> Result<Vec<Bag>, String> {
+        if let Some(id) = game_ids
+            .iter()
+            .find(|id| !self.games.iter().any(|game| game.id == **id))
+        {
+            return Err(format!("no game {}", id));
+        }
+        let minimal_bag =
+            self.minimal_bag_of(self.games.iter().filter(|game| game_ids.contains(&game.id)));
+        let minimums = minimal_bag.into_iter().collect::<Vec<_>>();
+        let mut bags = vec![];
+        let mut counts = vec![];
+        fill_bags(&minimums, budget, &mut counts, &mut bags);
+        Ok(bags)
+    }
+
+    /// The probability of each grab of each game under the bag, cubes being drawn without being
+    /// put back and returned to the bag between grabs.
+    pub fn grab_probabilities(&self, bag: &Bag) -> Vec<(u32, Vec<f64>)> {
+        self.games
+            .iter()
+            .map(|game| {
+                let probabilities = game.grabs.iter().map(|grab| grab.probability(bag));
+                (game.id, probabilities.collect())
+            })
+            .collect()
+    }
+}
+
+/// Adds every bag giving the colors left at least their minimum without going over the budget.
+fn fill_bags(minimums: &[(String, u32)], budget: u32, counts: &mut Vec<u32>, bags: &mut Vec<Bag>) {
+    let Some((_, minimum)) = minimums.get(counts.len()) else {
+        let bag = minimums
+            .iter()
+            .zip(counts.iter())
+            .map(|((color, _), count)| (color.clone(), *count));
+        bags.push(bag.collect());
+        return;
+    };
+    let needed_by_others = minimums[counts.len() + 1..]
+        .iter()
+        .map(|(_, minimum)| minimum)
+        .sum::<u32>();
+    let used = counts.iter().sum::<u32>();
+    for count in *minimum..=budget.saturating_sub(used + needed_by_others) {
+        counts.push(count);
+        fill_bags(minimums, budget, counts, bags);
+        counts.pop();
+    }
+}
+
+impl Day for Day2 {
+    fn make_day(input: impl Read) -> Self {
+        let games = std::io::BufReader::new(input)
+            .lines()
+            .map(|line| line.expect("doc should have lines").parse().unwrap())
+            .collect::<Vec<Game>>();
+        let colors = games
+            .iter()
+            .flat_map(|game| game.grabs.iter())
+            .flat_map(|grab| grab.nb_cubes_by_color.keys().cloned())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        Day2 {
+            games,
+            colors,
+            bag: parse_bag("red:12,green:13,blue:14").unwrap(),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            // the bag the games of part 1 are checked against
+            "bag" => {
+                self.bag = parse_bag(value)?;
+                Ok(())
+            }
+            _ => Err(format!("unknown parameter {}", name)),
+        }
+    }
+
+    fn input_spec() -> Spec {
+        let grab = r"\d+ [a-z]+(, \d+ [a-z]+)*";
+        Spec::default()
+            .block(Block::lines(
+                "game",
+                &format!(r"Game \d+: {}(; {})*", grab, grab),
+            ))
+            .check(check_games)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        (1..=size)
+            .map(|id| {
+                let grabs = (0..rng.gen_range(1..=6))
+                    .map(|_| {
+                        let mut colors = COLOR_NAMES;
+                        colors.shuffle(rng);
+                        colors[..rng.gen_range(1..=3)]
+                            .iter()
+                            .map(|color| format!("{} {}", rng.gen_range(1..=20), color))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                format!("Game {}: {}\n", id, grabs)
+            })
+            .collect()
+    }
+
+    fn solution1(&self) -> String {
+        let result: u32 = self
+            .games
+            .iter()
+            .filter(|game| game.grabs.iter().all(|grab| grab.is_acceptable(&self.bag)))
+            .map(|game| game.id)
+            .sum();
+        result.to_string()
+    }
+
+    fn solution2(&self) -> String {
+        let result: u32 = self
+            .games
+            .iter()
+            .map(|game| game.get_power(&self.colors))
+            .sum();
+        result.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::check_round_trip;
+
+    #[test]
+    fn test_day2_sol1() {
+        let games = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]
+        .join("\n");
+        let day2 = Day2::make_day(games.as_bytes());
+        assert_eq!(day2.solution1(), "8");
+    }
+
+    #[test]
+    fn test_day2_sol2() {
+        let games = [
+            "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green",
+            "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+            "Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red",
+            "Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red",
+            "Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green",
+        ]
+        .join("\n");
+        let day2 = Day2::make_day(games.as_bytes());
+        assert_eq!(day2.solution2(), "2286");
+    }
+
+    #[test]
+    fn test_display_round_trip() {
+        // the colors of the grabs are generated in any order
+        check_round_trip::<Day2, Game>(10, false, |line| line.parse().ok());
+        // zero is a count like any other
+        for (line, printed) in [
+            ("Game 1: 0 red, 1 blue", "Game 1: 1 blue, 0 red"),
+            ("Game 1: 0 red", "Game 1: 0 red"),
+        ] {
+            let game = line.parse::<Game>().unwrap();
+            assert_eq!(game.to_string(), printed);
+            assert_eq!(game.to_string().parse(), Ok(game));
+        }
+        assert!("Game 1: ".parse::<Game>().is_err());
+    }
+
+    #[test]
+    fn test_malformed_games() {
+        let error = |line: &str| line.parse::<Game>().unwrap_err();
+        assert_eq!(
+            error("Game 1 3 red"),
+            "Game 1 3 red should be Game <id>: <grabs>"
+        );
+        assert_eq!(error("Game x: 3 red"), "x should be a game id");
+        assert_eq!(error("Round 1: 3 red"), "Round 1 should be Game <id>");
+        assert_eq!(
+            error("Game 1: 3 red, 4 red"),
+            "red is counted twice in a grab"
+        );
+        assert_eq!(error("Game 1: 3 red cubes"), "unexpected cubes after 3 red");
+        // the same color in two grabs of a game is fine
+        assert!("Game 1: 3 red; 4 red".parse::<Game>().is_ok());
+
+        let problems = crate::days::lint(2023, 2, "Game 1: 1 red\nGame 2: 3 red, 4 blue, 1 red\n")
+            .unwrap()
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(problems, ["line 2: red is counted twice in a grab"]);
+    }
+
+    #[test]
+    fn test_open_colors() {
+        let input = "Game 1: 3 yellow, 4 red; 1 red, 2 purple\n\
+                     Game 2: 5 yellow; 2 purple\n\
+                     Game 3: 1 red\n";
+        let mut day2 = Day2::make_day(input.as_bytes());
+        assert_eq!(day2.colors, ["purple", "red", "yellow"]);
+        // the colors of the default bag only
+        assert_eq!(day2.solution1(), "3");
+        day2.set_param("bag", "yellow:4,red:4,purple:2").unwrap();
+        assert_eq!(day2.solution1(), "4");
+        // games 2 and 3 lack a color, their power being zero
+        assert_eq!(day2.solution2(), (3 * 4 * 2).to_string());
+        assert_eq!(
+            day2.set_param("bag", "red=4"),
+            Err("red=4 should be color:count".to_string())
+        );
+    }
+
+    fn example() -> Day2 {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green\n\
+                     Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue\n\
+                     Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red\n";
+        Day2::make_day(input.as_bytes())
+    }
+
+    #[test]
+    fn test_minimal_bag_and_bags_admitting() {
+        let day2 = example();
+        assert_eq!(format_bag(&day2.minimal_bag()), "blue:6,green:13,red:20");
+
+        // games 1 and 2 need 6 blue, 3 green and 4 red cubes, leaving 2 to spare
+        let bags = day2.bags_admitting(&[1, 2], 15).unwrap();
+        assert_eq!(
+            bags.iter().map(format_bag).collect::<Vec<_>>(),
+            [
+                "blue:6,green:3,red:4",
+                "blue:6,green:3,red:5",
+                "blue:6,green:3,red:6",
+                "blue:6,green:4,red:4",
+                "blue:6,green:4,red:5",
+                "blue:6,green:5,red:4",
+                "blue:7,green:3,red:4",
+                "blue:7,green:3,red:5",
+                "blue:7,green:4,red:4",
+                "blue:8,green:3,red:4",
+            ]
+        );
+        assert!(day2.bags_admitting(&[1, 2], 12).unwrap().is_empty());
+        assert_eq!(day2.bags_admitting(&[4], 30), Err("no game 4".to_string()));
+    }
+
+    #[test]
+    fn test_grab_probabilities() {
+        let day2 = example();
+        let bag = parse_bag("red:2,green:1,blue:1").unwrap();
+        let probabilities = day2.grab_probabilities(&bag);
+        let (id, game2) = &probabilities[1];
+        assert_eq!(*id, 2);
+        // 1 blue and 2 green out of 4 cubes cannot be drawn with a single green one
+        assert_eq!(game2[0], 0.);
+        let (_, game1) = &probabilities[0];
+        assert_eq!(game1.len(), 3);
+        assert!(game1.iter().all(|probability| *probability == 0.));
+
+        let bag = parse_bag("red:3,green:2,blue:4").unwrap();
+        // 6 blue cubes out of a bag holding 4
+        assert_eq!(day2.grab_probabilities(&bag)[0].1[1], 0.);
+        let probability = CubeGrab::from_str("1 red, 1 blue")
+            .unwrap()
+            .probability(&bag);
+        // 3 * 4 ways out of C(9, 2) = 36
+        assert!((probability - 12. / 36.).abs() < 1e-12);
+    }
+}
@@ -1,14 +1,20 @@
 use itertools::Itertools;
-use num::integer::lcm;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashSet;
 use std::collections::{HashMap, VecDeque};
-use std::fs::File;
+use std::fmt;
 use std::io::BufRead;
+use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+use crate::numeric::{self, Number, Overflow, Precision};
 
 #[derive(Debug)]
 pub struct Day20 {
     modules: HashMap<String, Module>,
+    precision: Precision,
 }
 
 fn handle_button_press(
@@ -39,7 +45,7 @@ fn handle_button_press(
     nb_pulses
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Module {
     module_type: ModuleType,
     id: String,
@@ -88,7 +94,20 @@ impl Module {
     }
 }
 
-#[derive(Debug, Clone)]
+impl fmt::Display for Module {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.module_type {
+            ModuleType::FlipFlop { .. } => write!(f, "%{}", self.id)?,
+            ModuleType::Conjunction { .. } => write!(f, "&{}", self.id)?,
+            ModuleType::Broadcast => write!(f, "broadcaster")?,
+            // outputs only appear as the destination of other modules
+            ModuleType::Output => return Ok(()),
+        }
+        write!(f, " -> {}", self.ids_outputs.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
 enum ModuleType {
     FlipFlop {
         state: bool,
@@ -132,9 +151,45 @@ enum Pulse {
     High = 1,
 }
 
+fn random_names(rng: &mut impl Rng, count: usize) -> Vec<String> {
+    let length = if count > 300 { 3 } else { 2 };
+    let mut names = HashSet::with_capacity(count);
+    let mut result = Vec::with_capacity(count);
+    while result.len() < count {
+        let name = (0..length)
+            .map(|_| char::from(b'a' + rng.gen_range(0..26)))
+            .collect::<String>();
+        if name != "rx" && names.insert(name.clone()) {
+            result.push(name);
+        }
+    }
+    result
+}
+
+fn check_modules(lines: &[&str]) -> Vec<Problem> {
+    let mut problems = vec![];
+    let mut definitions = HashMap::with_capacity(lines.len());
+    for (index, line) in lines.iter().enumerate() {
+        let module = Module::construct_from_line_with_empty_inputs(line);
+        if let Some(first_line) = definitions.insert(module.id.clone(), index + 1) {
+            problems.push(Problem::on_line(
+                index + 1,
+                format!(
+                    "module {} already defined on line {}",
+                    module.id, first_line
+                ),
+            ));
+        }
+    }
+    if !definitions.contains_key("broadcast") {
+        problems.push(Problem::on_input("no broadcaster"));
+    }
+    problems
+}
+
 impl Day for Day20 {
-    fn make_day(file: File) -> Self {
-        let mut modules: HashMap<String, Module> = std::io::BufReader::new(file)
+    fn make_day(input: impl Read) -> Self {
+        let mut modules: HashMap<String, Module> = std::io::BufReader::new(input)
             .lines()
             .map(|line| {
                 let module = Module::construct_from_line_with_empty_inputs(
@@ -152,12 +207,71 @@ impl Day for Day20 {
                 match modules.get_mut(id_output) {
                     Some(module) => module.add_input_to_conjunction(&id_input),
                     None => {
-                        modules.insert(id_output.clone(), Module::make_output_module(&id_output));
+                        modules.insert(id_output.clone(), Module::make_output_module(id_output));
                     }
                 }
             }
         }
-        Day20 { modules }
+        Day20 {
+            modules,
+            precision: Precision::default(),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "bigint" => {
+                self.precision = Precision::from_param(value)?;
+                Ok(())
+            }
+            _ => Err(format!("unknown parameter {}", name)),
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(Block::lines(
+                "module",
+                "(broadcaster|[%&][a-z]+) -> [a-z]+(, [a-z]+)*",
+            ))
+            .check(check_modules)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // As in the puzzle inputs, rx waits for binary counters of twelve flip-flops, each one
+        // reset by a conjunction when it reaches its period. The lcm of the periods of more than
+        // five of them needs --bigint.
+        const NB_BITS: usize = 12;
+        let nb_counters = size.max(1);
+        let mut names = random_names(rng, nb_counters * (NB_BITS + 2) + 1).into_iter();
+        let mut next_name = || names.next().unwrap();
+        let final_conjunction = next_name();
+
+        let mut first_flip_flops = vec![];
+        let mut module_lines = vec![];
+        for _ in 0..nb_counters {
+            let period = rng.gen_range(1 << (NB_BITS - 1)..1 << NB_BITS) | 1;
+            let flip_flops = (0..NB_BITS).map(|_| next_name()).collect_vec();
+            let hub = next_name();
+            let inverter = next_name();
+            let mut hub_outputs = vec![inverter.clone(), flip_flops[0].clone()];
+            for (bit, flip_flop) in flip_flops.iter().enumerate() {
+                let mut outputs = flip_flops.get(bit + 1).into_iter().cloned().collect_vec();
+                if (period >> bit) & 1 == 1 {
+                    outputs.push(hub.clone());
+                } else {
+                    hub_outputs.push(flip_flop.clone());
+                }
+                module_lines.push(format!("%{} -> {}", flip_flop, outputs.join(", ")));
+            }
+            module_lines.push(format!("&{} -> {}", hub, hub_outputs.join(", ")));
+            module_lines.push(format!("&{} -> {}", inverter, final_conjunction));
+            first_flip_flops.push(flip_flops[0].clone());
+        }
+        module_lines.push(format!("&{} -> rx", final_conjunction));
+        module_lines.push(format!("broadcaster -> {}", first_flip_flops.join(", ")));
+        module_lines.shuffle(rng);
+        module_lines.join("\n") + "\n"
     }
 
     fn solution1(&self) -> String {
@@ -183,12 +297,7 @@ impl Day for Day20 {
         let mut rx_ante_predecessors = self
             .modules
             .values()
-            .filter(|module| {
-                module
-                    .ids_outputs
-                    .iter()
-                    .any(|output| *output == rx_predecessor)
-            })
+            .filter(|module| module.ids_outputs.contains(&rx_predecessor))
             .map(|module| (module.id.clone(), 0))
             .collect();
         let mut modules = self.modules.clone();
@@ -200,26 +309,44 @@ impl Day for Day20 {
                 break;
             }
         }
-        let result = rx_ante_predecessors.values().fold(1, |x, y| lcm(x, *y));
-        result.to_string()
+        let periods = rx_ante_predecessors.into_values().collect_vec();
+        numeric::solve(
+            self.precision,
+            || lcm_of_periods(&periods),
+            || lcm_of_periods(&periods),
+        )
     }
 }
 
+fn lcm_of_periods<N: Number>(periods: &[u64]) -> Result<N, Overflow> {
+    periods.iter().try_fold(N::from(1), |lcm, period| {
+        numeric::lcm(&lcm, &N::from(*period as i64))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::check_round_trip;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
 
     #[test]
-    fn test_day20_sol1() {
-        let input = File::open("./inputs/day20/input_test.txt").expect("File not found");
-        let day = Day20::make_day(input);
-        assert_eq!(day.solution1(), "11687500");
+    fn test_display_round_trip() {
+        check_round_trip::<Day20, Module>(4, true, |line| {
+            Some(Module::construct_from_line_with_empty_inputs(line))
+        });
     }
 
     #[test]
-    fn test_day20_sol2() {
-        let input = File::open("./inputs/day20/input_test.txt").expect("File not found");
-        let day = Day20::make_day(input);
-        assert_eq!(day.solution2(), "sol2");
+    fn test_bigint() {
+        // the periods of six counters of twelve bits have an lcm of about 70 bits
+        let mut rng = StdRng::seed_from_u64(0);
+        let input = Day20::generate_input(&mut rng, 6);
+        let mut day = Day20::make_day(input.as_bytes());
+        let overflow = std::panic::catch_unwind(|| day.solution2());
+        assert!(crate::panic_message(overflow.unwrap_err()).contains("--bigint"));
+        day.set_param("bigint", "true").unwrap();
+        assert_eq!(day.solution2(), "38259956907032640975");
     }
 }
@@ -1,10 +1,12 @@
 use itertools::Itertools;
 use pathfinding::prelude::dijkstra_reach;
+use rand::Rng;
 use std::collections::HashSet;
-use std::fs::File;
 use std::io::BufRead;
+use std::io::Read;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
 
 type Num = i32;
 type Coordinate = (Num, Num);
@@ -22,6 +24,7 @@ pub struct Day21 {
     start: Coordinate,
     nb_rows: Num,
     nb_cols: Num,
+    nb_steps: Num,
 }
 
 impl Day21 {
@@ -43,6 +46,7 @@ impl Day21 {
             .count()
     }
 
+    #[allow(dead_code)]
     fn compute_nodes_at_exactly_n_steps_infinite(&self, nb_steps: Num) -> usize {
         let reached_nodes = dijkstra_reach(&self.start, |node, _| {
             neighbors(*node)
@@ -66,10 +70,10 @@ impl Day21 {
 }
 
 impl Day for Day21 {
-    fn make_day(file: File) -> Self {
+    fn make_day(input: impl Read) -> Self {
         let mut nb_rows = 0;
         let mut nb_cols = 0;
-        let (start, rocks): (Vec<_>, Vec<_>) = std::io::BufReader::new(file)
+        let (start, rocks): (Vec<_>, Vec<_>) = std::io::BufReader::new(input)
             .lines()
             .enumerate()
             .flat_map(|(row, line)| {
@@ -90,32 +94,67 @@ impl Day for Day21 {
             rocks,
             nb_rows,
             nb_cols,
+            nb_steps: 64,
         }
     }
 
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "nb_steps" => {
+                self.nb_steps = value.parse().map_err(|_| "should be a number")?;
+                Ok(())
+            }
+            _ => Err(format!("unknown parameter {}", name)),
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(Block::grid("garden", "[.#S]"))
+            .count('S', 1)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // odd side, so that the start sits in the middle of the garden
+        let size = size.max(1) | 1;
+        let center = size / 2;
+        (0..size)
+            .map(|row| {
+                (0..size)
+                    .map(|col| {
+                        if (row, col) == (center, center) {
+                            'S'
+                        } else if rng.gen_bool(0.1) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+
     fn solution1(&self) -> String {
-        self.compute_nodes_at_exactly_n_steps(64).to_string()
+        self.compute_nodes_at_exactly_n_steps(self.nb_steps)
+            .to_string()
     }
 
     fn solution2(&self) -> String {
-        self.compute_nodes_at_exactly_n_steps(64).to_string()
+        self.compute_nodes_at_exactly_n_steps(self.nb_steps)
+            .to_string()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_day21_sol1() {
-        let input = File::open("./inputs/day21/input_test.txt").expect("File not found");
-        let day = Day21::make_day(input);
-        assert_eq!(day.compute_nodes_at_exactly_n_steps(6), 16);
-    }
+    use std::fs::File;
 
     #[test]
     fn test_day21_sol2() {
-        let input = File::open("./inputs/day21/input_test.txt").expect("File not found");
+        let input = File::open("./fixtures/day21/example.txt").expect("File not found");
         let day = Day21::make_day(input);
         assert_eq!(day.compute_nodes_at_exactly_n_steps_infinite(6), 16);
         assert_eq!(day.compute_nodes_at_exactly_n_steps_infinite(10), 50);
@@ -131,7 +170,7 @@ mod tests {
 
     #[test]
     fn test_pattern() {
-        let input = File::open("./inputs/day21/input_test.txt").expect("File not found");
+        let input = File::open("./fixtures/day21/example.txt").expect("File not found");
         let day = Day21::make_day(input);
         assert_eq!(day.compute_nodes_at_exactly_n_steps_infinite(6), 16);
         assert_eq!(day.compute_nodes_at_exactly_n_steps_infinite(10), 50);
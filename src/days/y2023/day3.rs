@@ -0,0 +1,530 @@
+use rand::Rng;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::BufRead;
+use std::io::Read;
+use std::str::FromStr;
+
+use crate::days::Day;
+use crate::lint::{Block, Spec};
+use crate::numeric::{self, Overflow};
+
+pub struct Day3 {
+    schematic: Schematic,
+}
+
+/// The numbers and symbols of a schematic, with which numbers touch which symbols, found in a
+/// single pass over the grid.
+pub struct Schematic {
+    numbers: Vec<EngineNumber>,
+    symbols: Vec<EngineSymbol>,
+    /// For each symbol, the numbers around it.
+    numbers_of_symbol: Vec<Vec<usize>>,
+    /// For each number, the symbols around it.
+    symbols_of_number: Vec<Vec<usize>>,
+}
+
+/// Numbers and symbols touching each other, directly or through other ones.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Component {
+    pub numbers: Vec<usize>,
+    pub symbols: Vec<usize>,
+}
+
+impl Schematic {
+    pub fn from_input(input: impl Read) -> Schematic {
+        let engine_schematic = std::io::BufReader::new(input)
+            .lines()
+            .map(|line| {
+                line.expect("doc should have lines")
+                    .chars()
+                    .map(|ch| ch.into())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        Schematic::new(&engine_schematic)
+    }
+
+    fn new(engine_schematic: &[Vec<EngineElement>]) -> Schematic {
+        let mut numbers = vec![];
+        let mut symbols = vec![];
+        // the number covering each cell
+        let mut number_at = vec![];
+        for (row, line) in engine_schematic.iter().enumerate() {
+            let mut numbers_of_line = vec![None; line.len()];
+            let mut cur_num: Option<EngineNumber> = None;
+            for (column, element) in line.iter().enumerate() {
+                match (&mut cur_num, element) {
+                    (None, EngineElement::Digit(d)) => {
+                        cur_num = Some(EngineNumber {
+                            value: *d,
+                            row,
+                            start_col: column,
+                            end_col: column,
+                        });
+                    }
+                    (Some(number), EngineElement::Digit(d)) => {
+                        number.value = 10 * number.value + *d;
+                        number.end_col = column;
+                    }
+                    (_, element) => {
+                        numbers.extend(cur_num.take());
+                        if let EngineElement::Symbol(character) = element {
+                            symbols.push(EngineSymbol {
+                                character: *character,
+                                row,
+                                col: column,
+                            });
+                        }
+                    }
+                }
+                if cur_num.is_some() {
+                    numbers_of_line[column] = Some(numbers.len());
+                }
+            }
+            numbers.extend(cur_num);
+            number_at.push(numbers_of_line);
+        }
+
+        let mut symbols_of_number = vec![vec![]; numbers.len()];
+        let numbers_of_symbol = symbols
+            .iter()
+            .enumerate()
+            .map(|(symbol_index, symbol)| {
+                let mut around = vec![];
+                for row in symbol.row.saturating_sub(1)..=symbol.row + 1 {
+                    for col in symbol.col.saturating_sub(1)..=symbol.col + 1 {
+                        let number = number_at.get(row).and_then(|line| line.get(col)).copied();
+                        if let Some(Some(number)) = number {
+                            around.push(number);
+                        }
+                    }
+                }
+                // a number spans several cells around the symbol
+                around.sort_unstable();
+                around.dedup();
+                for number in around.iter() {
+                    symbols_of_number[*number].push(symbol_index);
+                }
+                around
+            })
+            .collect();
+        Schematic {
+            numbers,
+            symbols,
+            numbers_of_symbol,
+            symbols_of_number,
+        }
+    }
+
+    /// The part numbers, those next to a symbol.
+    pub fn numbers_next_to_symbols(&self) -> impl Iterator<Item = &EngineNumber> {
+        self.numbers
+            .iter()
+            .zip(self.symbols_of_number.iter())
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
+
+    /// The symbols with exactly `nb_neighbours` numbers around them.
+    pub fn symbols_with_neighbours(
+        &self,
+        nb_neighbours: usize,
+    ) -> impl Iterator<Item = usize> + '_ {
+        (0..self.symbols.len())
+            .filter(move |symbol| self.numbers_of_symbol[*symbol].len() == nb_neighbours)
+    }
+
+    /// The groups of symbols and of the parts around them connected through each other, sorted
+    /// by their first symbol.
+    pub fn components(&self) -> Vec<Component> {
+        let mut seen_symbols = vec![false; self.symbols.len()];
+        let mut seen_numbers = vec![false; self.numbers.len()];
+        let mut components = vec![];
+        for start in 0..self.symbols.len() {
+            if seen_symbols[start] {
+                continue;
+            }
+            seen_symbols[start] = true;
+            let mut component = Component {
+                numbers: vec![],
+                symbols: vec![],
+            };
+            let mut to_visit = vec![start];
+            while let Some(symbol) = to_visit.pop() {
+                component.symbols.push(symbol);
+                for number in self.numbers_of_symbol[symbol].iter().copied() {
+                    if seen_numbers[number] {
+                        continue;
+                    }
+                    seen_numbers[number] = true;
+                    component.numbers.push(number);
+                    for next_symbol in self.symbols_of_number[number].iter().copied() {
+                        if !seen_symbols[next_symbol] {
+                            seen_symbols[next_symbol] = true;
+                            to_visit.push(next_symbol);
+                        }
+                    }
+                }
+            }
+            component.numbers.sort_unstable();
+            component.symbols.sort_unstable();
+            components.push(component);
+        }
+        components
+    }
+}
+
+#[derive(Debug)]
+enum EngineElement {
+    Digit(u32),
+    Symbol(char),
+    Period,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct EngineNumber {
+    pub value: u32,
+    row: usize,
+    start_col: usize,
+    end_col: usize,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct EngineSymbol {
+    character: char,
+    row: usize,
+    col: usize,
+}
+
+impl Schematic {
+    pub fn number(&self, index: usize) -> &EngineNumber {
+        &self.numbers[index]
+    }
+
+    pub fn symbol(&self, index: usize) -> &EngineSymbol {
+        &self.symbols[index]
+    }
+
+    /// The numbers around a symbol.
+    pub fn neighbours(&self, symbol: usize) -> impl Iterator<Item = &EngineNumber> {
+        self.numbers_of_symbol[symbol]
+            .iter()
+            .map(|number| &self.numbers[*number])
+    }
+}
+
+/// How many numbers a symbol needs around it to follow a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NeighbourCount {
+    Exactly(usize),
+    AtLeast(usize),
+    AtMost(usize),
+}
+
+impl NeighbourCount {
+    fn accepts(&self, nb_neighbours: usize) -> bool {
+        match *self {
+            NeighbourCount::Exactly(n) => nb_neighbours == n,
+            NeighbourCount::AtLeast(n) => nb_neighbours >= n,
+            NeighbourCount::AtMost(n) => nb_neighbours <= n,
+        }
+    }
+}
+
+impl FromStr for NeighbourCount {
+    type Err = String;
+
+    /// Reads `n`, `>=n` or `<=n`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (constructor, count): (fn(usize) -> NeighbourCount, &str) =
+            if let Some(count) = s.strip_prefix(">=") {
+                (NeighbourCount::AtLeast, count)
+            } else if let Some(count) = s.strip_prefix("<=") {
+                (NeighbourCount::AtMost, count)
+            } else {
+                (NeighbourCount::Exactly, s)
+            };
+        count
+            .parse()
+            .map(constructor)
+            .map_err(|_| format!("{} should be n, >=n or <=n", s))
+    }
+}
+
+/// How the numbers around a symbol make up its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregation {
+    Product,
+    Sum,
+    Max,
+}
+
+impl FromStr for Aggregation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Aggregation::Product),
+            "sum" => Ok(Aggregation::Sum),
+            "max" => Ok(Aggregation::Max),
+            _ => Err(format!("{} should be product, sum or max", s)),
+        }
+    }
+}
+
+/// Which symbols count and how their value comes from the numbers around them, gears being the
+/// stars between exactly two numbers, valued by their product. Symbols without numbers around
+/// them have no value and never count.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolRule {
+    /// The characters of the symbols to consider, all of them when `None`.
+    pub symbols: Option<Vec<char>>,
+    pub neighbours: NeighbourCount,
+    pub aggregation: Aggregation,
+}
+
+impl SymbolRule {
+    pub fn gears() -> SymbolRule {
+        SymbolRule {
+            symbols: Some(vec!['*']),
+            neighbours: NeighbourCount::Exactly(2),
+            aggregation: Aggregation::Product,
+        }
+    }
+}
+
+/// The values of the symbols following a rule, in total and by symbol character.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RuleSummary {
+    pub total: i64,
+    pub by_symbol: BTreeMap<char, i64>,
+    pub nb_symbols: usize,
+}
+
+impl Schematic {
+    /// The value of every symbol following the rule, added up.
+    pub fn apply(&self, rule: &SymbolRule) -> Result<RuleSummary, Overflow> {
+        let mut summary = RuleSummary {
+            total: 0,
+            by_symbol: BTreeMap::new(),
+            nb_symbols: 0,
+        };
+        for (symbol, numbers) in self.symbols.iter().zip(self.numbers_of_symbol.iter()) {
+            let chosen = rule
+                .symbols
+                .as_ref()
+                .is_none_or(|characters| characters.contains(&symbol.character));
+            if !chosen || numbers.is_empty() || !rule.neighbours.accepts(numbers.len()) {
+                continue;
+            }
+            let values = numbers
+                .iter()
+                .map(|number| Ok(self.numbers[*number].value as i64));
+            let value = match rule.aggregation {
+                Aggregation::Product => numeric::product(values)?,
+                Aggregation::Sum => numeric::sum(values)?,
+                Aggregation::Max => values.flatten().max().unwrap_or(0),
+            };
+            summary.total = numeric::add(&summary.total, &value)?;
+            let by_symbol = summary.by_symbol.entry(symbol.character).or_insert(0);
+            *by_symbol = numeric::add(by_symbol, &value)?;
+            summary.nb_symbols += 1;
+        }
+        Ok(summary)
+    }
+}
+
+impl fmt::Display for EngineNumber {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at {}:{}",
+            self.value,
+            self.row + 1,
+            self.start_col + 1
+        )
+    }
+}
+
+impl fmt::Display for EngineSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at {}:{}", self.character, self.row + 1, self.col + 1)
+    }
+}
+
+impl From<char> for EngineElement {
+    fn from(value: char) -> Self {
+        match value {
+            '.' => EngineElement::Period,
+            '0'..='9' => EngineElement::Digit(value.to_digit(10).unwrap()),
+            c => EngineElement::Symbol(c),
+        }
+    }
+}
+
+impl Day for Day3 {
+    fn make_day(input: impl Read) -> Self {
+        Day3 {
+            schematic: Schematic::from_input(input),
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::grid("schematic", r"\S"))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        const SYMBOLS: &[u8] = b"*#+$/@=%&-";
+        let mut schematic = String::with_capacity(size * (size + 1));
+        for _ in 0..size {
+            let mut line = String::with_capacity(size);
+            while line.len() < size {
+                let remaining = size - line.len();
+                if remaining >= 2 && rng.gen_bool(0.15) {
+                    let nb_digits = rng.gen_range(1..=(remaining - 1).min(3)) as u32;
+                    let number = rng.gen_range(10u32.pow(nb_digits - 1)..10u32.pow(nb_digits));
+                    line += &number.to_string();
+                    line.push('.');
+                } else if rng.gen_bool(0.05) {
+                    line.push(SYMBOLS[rng.gen_range(0..SYMBOLS.len())].into());
+                } else {
+                    line.push('.');
+                }
+            }
+            schematic += &line;
+            schematic.push('\n');
+        }
+        schematic
+    }
+
+    fn solution1(&self) -> String {
+        let result: u32 = self
+            .schematic
+            .numbers_next_to_symbols()
+            .map(|engine_number| engine_number.value)
+            .sum();
+        result.to_string()
+    }
+
+    fn solution2(&self) -> String {
+        let gears = self.schematic.apply(&SymbolRule::gears());
+        numeric::answer(gears.map(|gears| gears.total))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn test_schematic() {
+        let schematic = Day3::make_day(EXAMPLE.as_bytes()).schematic;
+        assert_eq!(schematic.numbers.len(), 10);
+        assert_eq!(
+            schematic.numbers[4],
+            EngineNumber {
+                value: 617,
+                row: 4,
+                start_col: 0,
+                end_col: 2
+            }
+        );
+        let parts = schematic
+            .numbers_next_to_symbols()
+            .map(|number| number.value)
+            .collect::<Vec<_>>();
+        assert_eq!(parts, [467, 35, 633, 617, 592, 755, 664, 598]);
+        // the first star touches 467 and 35, the second one 617 only
+        assert_eq!(schematic.numbers_of_symbol[0], [0, 2]);
+        assert_eq!(schematic.numbers_of_symbol[2], [4]);
+        assert_eq!(
+            schematic.symbols_with_neighbours(1).collect::<Vec<_>>(),
+            [1, 2, 3, 4]
+        );
+        assert_eq!(
+            schematic.symbols_with_neighbours(2).collect::<Vec<_>>(),
+            [0, 5]
+        );
+    }
+
+    #[test]
+    fn test_components() {
+        let schematic = Day3::make_day("12*3..\n......\n4#5+.6\n".as_bytes()).schematic;
+        assert_eq!(
+            schematic.components(),
+            [
+                Component {
+                    numbers: vec![0, 1],
+                    symbols: vec![0]
+                },
+                Component {
+                    numbers: vec![2, 3],
+                    symbols: vec![1, 2]
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rules() {
+        let schematic = Day3::make_day(EXAMPLE.as_bytes()).schematic;
+        assert_eq!(
+            schematic.apply(&SymbolRule::gears()),
+            Ok(RuleSummary {
+                total: 467835,
+                by_symbol: BTreeMap::from([('*', 467835)]),
+                nb_symbols: 2
+            })
+        );
+
+        let rule = SymbolRule {
+            symbols: None,
+            neighbours: ">=1".parse().unwrap(),
+            aggregation: "sum".parse().unwrap(),
+        };
+        let summary = schematic.apply(&rule).unwrap();
+        assert_eq!(summary.total, 4361);
+        assert_eq!(
+            summary.by_symbol,
+            BTreeMap::from([
+                ('#', 633),
+                ('$', 664),
+                ('*', 467 + 35 + 617 + 755 + 598),
+                ('+', 592)
+            ])
+        );
+
+        let rule = SymbolRule {
+            symbols: Some(vec!['*', '#']),
+            neighbours: "<=1".parse().unwrap(),
+            aggregation: Aggregation::Max,
+        };
+        assert_eq!(schematic.apply(&rule).unwrap().total, 633 + 617);
+
+        // an empty product is not a value
+        let schematic = Schematic::from_input("*...\n....\n..5#\n".as_bytes());
+        let rule = SymbolRule {
+            symbols: None,
+            neighbours: "<=1".parse().unwrap(),
+            aggregation: Aggregation::Product,
+        };
+        let summary = schematic.apply(&rule).unwrap();
+        assert_eq!((summary.total, summary.nb_symbols), (5, 1));
+        assert_eq!(summary.by_symbol, BTreeMap::from([('#', 5)]));
+        assert!("2+".parse::<NeighbourCount>().is_err());
+        assert!("min".parse::<Aggregation>().is_err());
+    }
+}
@@ -0,0 +1,588 @@
+use itertools::Itertools;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::cmp::Reverse;
+use std::fmt;
+use std::io::BufRead;
+use std::io::Read;
+use std::str::FromStr;
+
+use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+use crate::numeric::{self, Number, Overflow, Precision};
+
+pub struct Day4 {
+    cards: Vec<Card>,
+    precision: Precision,
+    past_end: PastEnd,
+}
+
+/// What becomes of the copies a card wins past the end of the table.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PastEnd {
+    /// They make the input invalid.
+    #[default]
+    Error,
+    /// They are lost.
+    Clamp,
+    /// They go to the first cards again, counted but won after those were scratched, so that they
+    /// win nothing.
+    Wrap,
+}
+
+impl FromStr for PastEnd {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(PastEnd::Error),
+            "clamp" => Ok(PastEnd::Clamp),
+            "wrap" => Ok(PastEnd::Wrap),
+            _ => Err(format!("{} should be error, clamp or wrap", s)),
+        }
+    }
+}
+
+/// How a card took part in the cascade of copies.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CardStats<N> {
+    pub id: u32,
+    /// The copies won from the cards before it, the original left out.
+    pub copies_won: N,
+    /// The copies of other cards its instances won.
+    pub copies_given: N,
+}
+
+/// The copies of each card once they are all scratched.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Cascade<N> {
+    pub cards: Vec<CardStats<N>>,
+    /// The ids of the longest chain of cards each winning a copy of the next one.
+    pub longest_chain: Vec<u32>,
+}
+
+impl<N: Number> Cascade<N> {
+    pub fn total(&self) -> Result<N, Overflow> {
+        numeric::sum(
+            self.cards
+                .iter()
+                .map(|card| numeric::add(&card.copies_won, &N::from(1))),
+        )
+    }
+}
+
+/// Why the cards could not all be scratched.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CascadeError {
+    /// A card wins copies past the end of the table while those are errors.
+    PastEnd {
+        card: u32,
+        nb_copies: usize,
+    },
+    Overflow(Overflow),
+}
+
+impl From<Overflow> for CascadeError {
+    fn from(overflow: Overflow) -> Self {
+        CascadeError::Overflow(overflow)
+    }
+}
+
+impl fmt::Display for CascadeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CascadeError::PastEnd { card, nb_copies } => write!(
+                f,
+                "card {} wins {} copies past the end of the table",
+                card, nb_copies
+            ),
+            CascadeError::Overflow(overflow) => write!(f, "{}", overflow),
+        }
+    }
+}
+
+pub type CardNumber = u32;
+
+/// Both sides of a card arranged for counting the numbers they share, a number appearing twice on
+/// the same side being counted once.
+#[derive(Debug, PartialEq, Eq)]
+pub enum MatchIndex {
+    /// Bit `n` is set for each number `n`, all of them below 128.
+    Bits { winning: u128, found: u128 },
+    Sorted {
+        winning: Vec<CardNumber>,
+        found: Vec<CardNumber>,
+    },
+}
+
+impl MatchIndex {
+    fn new(winning_numbers: &[CardNumber], found_numbers: &[CardNumber]) -> MatchIndex {
+        let mut all_numbers = winning_numbers.iter().chain(found_numbers);
+        if all_numbers.all(|number| *number < u128::BITS) {
+            let bits = |numbers: &[CardNumber]| {
+                numbers
+                    .iter()
+                    .fold(0u128, |bits, number| bits | 1 << number)
+            };
+            MatchIndex::Bits {
+                winning: bits(winning_numbers),
+                found: bits(found_numbers),
+            }
+        } else {
+            let sorted = |numbers: &[CardNumber]| {
+                let mut numbers = numbers.to_vec();
+                numbers.sort_unstable();
+                numbers.dedup();
+                numbers
+            };
+            MatchIndex::Sorted {
+                winning: sorted(winning_numbers),
+                found: sorted(found_numbers),
+            }
+        }
+    }
+
+    fn nb_matches(&self) -> u32 {
+        match self {
+            MatchIndex::Bits { winning, found } => (winning & found).count_ones(),
+            MatchIndex::Sorted { winning, found } => winning
+                .iter()
+                .merge_join_by(found, |winning, found| winning.cmp(found))
+                .filter(|numbers| numbers.is_both())
+                .count() as u32,
+        }
+    }
+
+    /// The winning and the found numbers, in increasing order and without duplicates.
+    fn numbers(&self) -> (Vec<CardNumber>, Vec<CardNumber>) {
+        match self {
+            MatchIndex::Bits { winning, found } => {
+                let numbers = |bits: u128| (0..u128::BITS).filter(|n| bits >> n & 1 == 1).collect();
+                (numbers(*winning), numbers(*found))
+            }
+            MatchIndex::Sorted { winning, found } => (winning.clone(), found.clone()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Card {
+    id: u32,
+    index: MatchIndex,
+}
+
+/// The winning and the found numbers of a card, as they are listed.
+pub type CardNumbers = (Vec<CardNumber>, Vec<CardNumber>);
+
+/// The numbers of every card of an input, to compare the ways of counting their matches on them.
+pub fn read_card_numbers(input: &str) -> Vec<CardNumbers> {
+    input
+        .lines()
+        .map(|line| {
+            let (_, winning_numbers, found_numbers) = parse_card(line).expect("card line expected");
+            (winning_numbers, found_numbers)
+        })
+        .collect()
+}
+
+/// The matching numbers of all the cards together, looking for each winning number among the
+/// found ones.
+pub fn count_matches_by_search(cards: &[CardNumbers]) -> u64 {
+    cards
+        .iter()
+        .map(|(winning_numbers, found_numbers)| {
+            winning_numbers
+                .iter()
+                .filter(|winning_number| found_numbers.contains(winning_number))
+                .count() as u64
+        })
+        .sum()
+}
+
+pub fn index_cards(cards: &[CardNumbers]) -> Vec<MatchIndex> {
+    cards
+        .iter()
+        .map(|(winning_numbers, found_numbers)| MatchIndex::new(winning_numbers, found_numbers))
+        .collect()
+}
+
+/// The matching numbers of all the cards together, intersecting the sides of their indexes.
+pub fn count_matches_by_index(indexes: &[MatchIndex]) -> u64 {
+    indexes.iter().map(|index| index.nb_matches() as u64).sum()
+}
+
+impl Card {
+    fn new(id: u32, winning_numbers: &[CardNumber], found_numbers: &[CardNumber]) -> Card {
+        Card {
+            id,
+            index: MatchIndex::new(winning_numbers, found_numbers),
+        }
+    }
+
+    fn compute_number_of_winning_in_found_numbers(&self) -> u32 {
+        self.index.nb_matches()
+    }
+
+    fn compute_score<N: Number>(&self) -> Result<N, Overflow> {
+        match self.compute_number_of_winning_in_found_numbers() {
+            0 => Ok(N::from(0)),
+            n => numeric::pow(N::from(2), n as usize - 1),
+        }
+    }
+}
+
+impl Day4 {
+    fn total_score<N: Number>(&self) -> Result<N, Overflow> {
+        numeric::sum(self.cards.iter().map(|card| card.compute_score()))
+    }
+
+    /// The indices of the cards each card wins a copy of, following the policy past the end.
+    fn won_cards(&self) -> Result<Vec<Vec<usize>>, CascadeError> {
+        let nb_cards = self.cards.len();
+        self.cards
+            .iter()
+            .enumerate()
+            .map(|(card_index, card)| {
+                let nb_matches = card.compute_number_of_winning_in_found_numbers() as usize;
+                let won = card_index + 1..=card_index + nb_matches;
+                match self.past_end {
+                    _ if card_index + nb_matches < nb_cards => Ok(won.collect()),
+                    PastEnd::Error => Err(CascadeError::PastEnd {
+                        card: card.id,
+                        nb_copies: card_index + nb_matches + 1 - nb_cards,
+                    }),
+                    PastEnd::Clamp => Ok(won.filter(|index| *index < nb_cards).collect()),
+                    PastEnd::Wrap => Ok(won.map(|index| index % nb_cards).collect()),
+                }
+            })
+            .collect()
+    }
+
+    /// Scratches every card, failing on copies won past the end when they are errors.
+    pub fn cascade<N: Number>(&self) -> Result<Cascade<N>, CascadeError> {
+        let won_cards = self.won_cards()?;
+        Ok(cascade(&self.cards, &won_cards)?)
+    }
+
+    fn count_cards<N: Number>(&self, won_cards: &[Vec<usize>]) -> Result<N, Overflow> {
+        cascade::<N>(&self.cards, won_cards)?.total()
+    }
+}
+
+fn cascade<N: Number>(cards: &[Card], won_cards: &[Vec<usize>]) -> Result<Cascade<N>, Overflow> {
+    let mut nb_cards = vec![N::from(1); cards.len()];
+    let mut copies_given = Vec::with_capacity(cards.len());
+    // the longest chain ending with each card, and the card before it there
+    let mut chain_lengths = vec![1; cards.len()];
+    let mut previous_in_chain = vec![None; cards.len()];
+    for (card_index, won) in won_cards.iter().enumerate() {
+        let nb_card = nb_cards[card_index].clone();
+        for next_card in won.iter().copied() {
+            nb_cards[next_card] = numeric::add(&nb_cards[next_card], &nb_card)?;
+            if next_card > card_index && chain_lengths[card_index] + 1 > chain_lengths[next_card] {
+                chain_lengths[next_card] = chain_lengths[card_index] + 1;
+                previous_in_chain[next_card] = Some(card_index);
+            }
+        }
+        copies_given.push(numeric::mul(&nb_card, &N::from(won.len() as i64))?);
+    }
+
+    let mut longest_chain = vec![];
+    let mut chain_end =
+        (0..cards.len()).max_by_key(|index| (chain_lengths[*index], Reverse(*index)));
+    while let Some(index) = chain_end {
+        longest_chain.push(cards[index].id);
+        chain_end = previous_in_chain[index];
+    }
+    longest_chain.reverse();
+    let cards = cards
+        .iter()
+        .zip(nb_cards)
+        .zip(copies_given)
+        .map(|((card, nb_card), copies_given)| {
+            Ok(CardStats {
+                id: card.id,
+                copies_won: numeric::sub(&nb_card, &N::from(1))?,
+                copies_given,
+            })
+        })
+        .collect::<Result<_, Overflow>>()?;
+    Ok(Cascade {
+        cards,
+        longest_chain,
+    })
+}
+/// The id, the winning numbers and the found numbers of a card line.
+fn parse_card(s: &str) -> Option<(u32, Vec<CardNumber>, Vec<CardNumber>)> {
+    let first_split = s.split(&[':', '|']).collect::<Vec<_>>();
+    let id = first_split[0]
+        .split_whitespace()
+        .last()
+        .and_then(|id_str| id_str.parse().ok())?;
+    let winning_numbers = first_split[1]
+        .split_whitespace()
+        .map(|nb_str| {
+            nb_str
+                .parse()
+                .expect("number in winning numbers part expected")
+        })
+        .collect();
+    let found_numbers = first_split[2]
+        .split_whitespace()
+        .map(|nb_str| {
+            nb_str
+                .parse()
+                .expect("number in found numbers part expected")
+        })
+        .collect();
+    Some((id, winning_numbers, found_numbers))
+}
+
+impl FromStr for Card {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, winning_numbers, found_numbers) = parse_card(s).ok_or(())?;
+        Ok(Card::new(id, &winning_numbers, &found_numbers))
+    }
+}
+
+fn format_numbers(numbers: &[CardNumber]) -> String {
+    numbers
+        .iter()
+        .map(|number| format!("{:>2}", number))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Prints the numbers of the card in increasing order, without their duplicates.
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (winning_numbers, found_numbers) = self.index.numbers();
+        write!(
+            f,
+            "Card {:>3}: {} | {}",
+            self.id,
+            format_numbers(&winning_numbers),
+            format_numbers(&found_numbers)
+        )
+    }
+}
+
+fn check_cards(lines: &[&str]) -> Vec<Problem> {
+    // a card wins copies of the next cards, which have to exist
+    lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let card: Card = line.parse().ok()?;
+            let nb_matches = card.compute_number_of_winning_in_found_numbers() as usize;
+            let nb_next_cards = lines.len() - 1 - index;
+            (nb_matches > nb_next_cards).then(|| {
+                Problem::on_line(
+                    index + 1,
+                    format!(
+                        "{} matching numbers but only {} cards after this one",
+                        nb_matches, nb_next_cards
+                    ),
+                )
+            })
+        })
+        .collect()
+}
+
+impl Day for Day4 {
+    fn make_day(input: impl Read) -> Self {
+        let cards = std::io::BufReader::new(input)
+            .lines()
+            .map(|line| line.expect("doc should have lines").parse().unwrap())
+            .collect();
+        Day4 {
+            cards,
+            precision: Precision::default(),
+            past_end: PastEnd::default(),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "bigint" => {
+                self.precision = Precision::from_param(value)?;
+                Ok(())
+            }
+            // what becomes of the copies won past the last card
+            "past_end" => {
+                self.past_end = value.parse()?;
+                Ok(())
+            }
+            _ => Err(format!("unknown parameter {}", name)),
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(Block::lines("card", r"Card +\d+:( +\d+)+ \|( +\d+)+"))
+            .check(check_cards)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        let mut cards = String::new();
+        for card_index in 0..size {
+            let mut numbers = (1..=99).collect::<Vec<CardNumber>>();
+            numbers.shuffle(rng);
+            let (winning_numbers, other_numbers) = numbers.split_at(10);
+            // few matches per card on average, and never more than the cards left to win
+            let nb_matches = if rng.gen_bool(0.25) {
+                rng.gen_range(1..=4).min(size - 1 - card_index)
+            } else {
+                0
+            };
+            let mut found_numbers = winning_numbers[..nb_matches]
+                .iter()
+                .chain(other_numbers[..25 - nb_matches].iter())
+                .copied()
+                .collect::<Vec<_>>();
+            found_numbers.shuffle(rng);
+            // in the order they were drawn, which the cards do not keep
+            cards += &format!(
+                "Card {:>3}: {} | {}\n",
+                card_index + 1,
+                format_numbers(winning_numbers),
+                format_numbers(&found_numbers)
+            );
+        }
+        cards
+    }
+
+    fn solution1(&self) -> String {
+        numeric::solve(self.precision, || self.total_score(), || self.total_score())
+    }
+
+    fn solution2(&self) -> String {
+        let won_cards = self.won_cards().unwrap_or_else(|error| panic!("{}", error));
+        numeric::solve(
+            self.precision,
+            || self.count_cards(&won_cards),
+            || self.count_cards(&won_cards),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::check_round_trip;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_display_round_trip() {
+        check_round_trip::<Day4, Card>(10, false, |line| line.parse().ok());
+        let card = "Card 1: 41 48 17 | 83 86 48 6 83".parse::<Card>().unwrap();
+        assert_eq!(card.to_string(), "Card   1: 17 41 48 |  6 48 83 86");
+    }
+
+    #[test]
+    fn test_bigint() {
+        // every card matches all of the next ones, doubling the copies of the last card each time
+        let input = (1..=70)
+            .map(|id| {
+                let numbers = format_numbers(&((id + 1)..=70).collect::<Vec<_>>());
+                format!("Card {}: {} | {}\n", id, numbers, numbers)
+            })
+            .collect::<String>();
+        let mut day = Day4::make_day(input.as_bytes());
+        let overflow = std::panic::catch_unwind(|| day.solution2());
+        assert!(crate::panic_message(overflow.unwrap_err()).contains("--bigint"));
+        day.set_param("bigint", "true").unwrap();
+        assert_eq!(day.solution2(), ((1u128 << 70) - 1).to_string());
+    }
+
+    #[test]
+    fn test_match_index() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let input = Day4::generate_input(&mut rng, 1000);
+        let day = Day4::make_day(input.as_bytes());
+        assert!(matches!(day.cards[0].index, MatchIndex::Bits { .. }));
+        let cards = read_card_numbers(&input);
+        assert_eq!(
+            count_matches_by_index(&index_cards(&cards)),
+            count_matches_by_search(&cards)
+        );
+
+        let card = |line: &str| line.parse::<Card>().unwrap();
+        let small = card("Card 1: 0 63 64 127 | 127 1 64 0 64");
+        assert!(matches!(small.index, MatchIndex::Bits { .. }));
+        assert_eq!(small.compute_number_of_winning_in_found_numbers(), 3);
+        let large = "Card 2: 5 100000 128 7 | 128 8 100000 5 5";
+        assert!(matches!(card(large).index, MatchIndex::Sorted { .. }));
+        assert_eq!(card(large).compute_number_of_winning_in_found_numbers(), 3);
+        assert_eq!(count_matches_by_search(&read_card_numbers(large)), 3);
+        assert_eq!(
+            card("Card 3: 1 | 2").compute_number_of_winning_in_found_numbers(),
+            0
+        );
+    }
+
+    fn card_line(id: u32, nb_matches: u32) -> String {
+        let found = (1..=5).collect::<Vec<_>>();
+        let winning = (1..=5).map(|n| n + 10 * (n > nb_matches) as u32);
+        format!(
+            "Card {}: {} | {}\n",
+            id,
+            format_numbers(&winning.collect::<Vec<_>>()),
+            format_numbers(&found)
+        )
+    }
+
+    fn cards_with_matches(matches: &[u32]) -> String {
+        matches
+            .iter()
+            .enumerate()
+            .map(|(index, nb_matches)| card_line(index as u32 + 1, *nb_matches))
+            .collect()
+    }
+
+    #[test]
+    fn test_cascade() {
+        // the matches of the example
+        let input = cards_with_matches(&[4, 2, 2, 1, 0, 0]);
+        let day = Day4::make_day(input.as_bytes());
+        let cascade = day.cascade::<i64>().unwrap();
+        assert_eq!(cascade.total(), Ok(30));
+        let copies_won = cascade.cards.iter().map(|card| card.copies_won);
+        assert_eq!(copies_won.collect::<Vec<_>>(), [0, 1, 3, 7, 13, 0]);
+        let copies_given = cascade.cards.iter().map(|card| card.copies_given);
+        assert_eq!(copies_given.collect::<Vec<_>>(), [4, 4, 8, 8, 0, 0]);
+        assert_eq!(cascade.longest_chain, [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_past_end() {
+        let input = cards_with_matches(&[1, 3, 1]);
+        let mut day = Day4::make_day(input.as_bytes());
+        let error = day.cascade::<i64>().unwrap_err();
+        assert_eq!(
+            error,
+            CascadeError::PastEnd {
+                card: 2,
+                nb_copies: 2
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "card 2 wins 2 copies past the end of the table"
+        );
+        day.set_param("past_end", "clamp").unwrap();
+        // card 2 only gives its 2 instances to card 3, and card 3 nothing
+        assert_eq!(day.solution2(), (1 + 2 + 3).to_string());
+        day.set_param("past_end", "wrap").unwrap();
+        // card 2 gives its 2 instances to cards 3, 1 and itself, and card 3 its 3 instances to
+        // card 1
+        let cascade = day.cascade::<i64>().unwrap();
+        let copies_won = cascade.cards.iter().map(|card| card.copies_won);
+        assert_eq!(copies_won.collect::<Vec<_>>(), [5, 3, 2]);
+        assert_eq!(cascade.longest_chain, [1, 2, 3]);
+        assert!(day.set_param("past_end", "bounce").is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use rand::Rng;
+use std::io::BufRead;
+use std::io::Read;
+use std::ops::Range;
+use std::str::FromStr;
+
+use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+use crate::numeric::{self, Overflow};
+
+// locations are all in the ranges of the input, so computing them never needs a big integer
+type Quantity = i64;
+
+pub struct Day5 {
+    start_seeds: Vec<Quantity>,
+    transformers: Vec<Transformer>,
+}
+
+impl Day5 {
+    fn convert_quantity(&self, seed: Quantity) -> Result<Quantity, Overflow> {
+        self.transformers
+            .iter()
+            .try_fold(seed, |curr_quantity, transformer| {
+                transformer.convert_quantity(curr_quantity)
+            })
+    }
+
+    fn get_ranges(&self) -> Result<Vec<Range<Quantity>>, Overflow> {
+        self.start_seeds
+            .chunks(2)
+            .map(|chunk| Ok(chunk[0]..numeric::add(&chunk[0], &chunk[1])?))
+            .collect()
+    }
+
+    fn lowest_location(&self) -> Result<Quantity, Overflow> {
+        let mut ranges = self.get_ranges()?;
+        for transformer in self.transformers.iter() {
+            let mut new_ranges = vec![];
+            for range in ranges {
+                new_ranges.append(&mut transformer.map_range(range)?);
+            }
+            ranges = new_ranges;
+        }
+        Ok(ranges
+            .iter()
+            .map(|range| range.start)
+            .min()
+            .expect("there is at least one seed"))
+    }
+}
+
+#[derive(Debug)]
+struct Transformer {
+    transformer_lines: Vec<TransformerLine>,
+}
+
+impl Transformer {
+    fn convert_quantity(&self, quantity: Quantity) -> Result<Quantity, Overflow> {
+        for transformer_line in self.transformer_lines.iter() {
+            if quantity >= transformer_line.source_start
+                && quantity < transformer_line.source_end()?
+            {
+                return numeric::add(
+                    &(quantity - transformer_line.source_start),
+                    &transformer_line.destination_start,
+                );
+            }
+        }
+        Ok(quantity)
+    }
+
+    fn map_range(&self, range: Range<Quantity>) -> Result<Vec<Range<Quantity>>, Overflow> {
+        let mut mapped_ranges = vec![];
+        let mut remaining_ranges = vec![range];
+        for line in self.transformer_lines.iter() {
+            let mut new_ranges = vec![];
+            for remaining_range in remaining_ranges {
+                let mut transformer_result = line.map_range(remaining_range)?;
+                if let Some(mapped_range) = transformer_result.mapped_range {
+                    mapped_ranges.push(mapped_range);
+                }
+                new_ranges.append(&mut transformer_result.remaining_ranges);
+            }
+            remaining_ranges = new_ranges;
+        }
+        mapped_ranges.append(&mut remaining_ranges);
+        merge_ranges(&mut mapped_ranges);
+        Ok(mapped_ranges)
+    }
+}
+
+fn merge_ranges(ranges: &mut Vec<Range<Quantity>>) {
+    ranges.sort_unstable_by_key(|range| range.start);
+    let mut idx = 0;
+    while idx < ranges.len() - 1 {
+        if ranges[idx].end >= ranges[idx + 1].start {
+            ranges[idx].end = ranges[idx + 1].end;
+            ranges.remove(idx + 1);
+        } else {
+            idx += 1;
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct TransformerLine {
+    destination_start: Quantity,
+    source_start: Quantity,
+    length: Quantity,
+}
+
+#[derive(Debug)]
+struct RangeTransformResult {
+    mapped_range: Option<Range<Quantity>>,
+    remaining_ranges: Vec<Range<Quantity>>,
+}
+
+impl TransformerLine {
+    fn source_end(&self) -> Result<Quantity, Overflow> {
+        numeric::add(&self.source_start, &self.length)
+    }
+
+    fn map_range(&self, range: Range<Quantity>) -> Result<RangeTransformResult, Overflow> {
+        let source_end = self.source_end()?;
+        let left_part = range.start.min(self.source_start)..range.end.min(self.source_start);
+        let right_part = range.start.max(source_end)..range.end.max(source_end);
+        let inner_part = range.start.max(self.source_start)..range.end.min(source_end);
+        let diff = self.destination_start - self.source_start;
+
+        let mut remaining_ranges = vec![];
+        if !left_part.is_empty() {
+            remaining_ranges.push(left_part);
+        }
+        if !right_part.is_empty() {
+            remaining_ranges.push(right_part);
+        }
+
+        let mapped_range = if inner_part.is_empty() {
+            None
+        } else {
+            Some(numeric::add(&inner_part.start, &diff)?..numeric::add(&inner_part.end, &diff)?)
+        };
+        Ok(RangeTransformResult {
+            mapped_range,
+            remaining_ranges,
+        })
+    }
+}
+impl FromStr for TransformerLine {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let numbers = s
+            .split_whitespace()
+            .map(|nb_str| nb_str.parse().unwrap())
+            .collect::<Vec<_>>();
+        let result = TransformerLine {
+            destination_start: numbers[0],
+            source_start: numbers[1],
+            length: numbers[2],
+        };
+        Ok(result)
+    }
+}
+
+fn check_seeds(lines: &[&str]) -> Vec<Problem> {
+    let nb_seeds = lines[0].split_whitespace().count() - 1;
+    if nb_seeds % 2 == 1 {
+        vec![Problem::on_line(
+            1,
+            "odd number of seeds, while part 2 reads them as pairs",
+        )]
+    } else {
+        vec![]
+    }
+}
+
+impl Day for Day5 {
+    fn make_day(input: impl Read) -> Self {
+        let document_lines = std::io::BufReader::new(input)
+            .lines()
+            .map(|line| line.expect("doc should have lines"))
+            .collect::<Vec<_>>();
+        let start_seeds = document_lines[0]
+            .split_once(": ")
+            .expect("should have colon")
+            .1
+            .split_whitespace()
+            .map(|nb_str| nb_str.parse().expect("should be seed number"))
+            .collect();
+        let mut index = 1;
+        let mut transformers = Vec::with_capacity(6);
+        while index < document_lines.len() {
+            if document_lines[index].is_empty() {
+                index += 1;
+            } else {
+                index += 1;
+                let mut transformer_lines = Vec::with_capacity(20);
+                while index < document_lines.len() && !document_lines[index].is_empty() {
+                    transformer_lines.push(
+                        document_lines[index]
+                            .parse()
+                            .expect("should be parsable as transformer line"),
+                    );
+                    index += 1;
+                }
+                transformer_lines.sort_unstable_by_key(|tr: &TransformerLine| tr.source_start);
+                transformers.push(Transformer { transformer_lines });
+            }
+        }
+        Day5 {
+            start_seeds,
+            transformers,
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(Block::starting_with("seeds", r"seeds:( \d+)+"))
+            .block(
+                Block::starting_with("map header", r"[a-z0-9]+-to-[a-z0-9]+ map:")
+                    .then_lines("range mapping", r"\d+ \d+ \d+"),
+            )
+            .repeat_last_block()
+            .check(check_seeds)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        const CATEGORIES: [&str; 8] = [
+            "seed",
+            "soil",
+            "fertilizer",
+            "water",
+            "light",
+            "temperature",
+            "humidity",
+            "location",
+        ];
+        const MAX_QUANTITY: Quantity = 1 << 32;
+        let category_name = |index: usize| match CATEGORIES.get(index) {
+            Some(name) => name.to_string(),
+            None => format!("category{}", index),
+        };
+
+        let seeds = (0..5)
+            .map(|_| {
+                let start = rng.gen_range(0..MAX_QUANTITY / 2);
+                let length = rng.gen_range(1..MAX_QUANTITY / 16);
+                format!("{} {}", start, length)
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut almanac = format!("seeds: {}\n", seeds);
+
+        // each layer maps some of the segments between random cuts, the others are left as is
+        for layer in 0..size {
+            let mut cuts = (0..rng.gen_range(2..=10))
+                .map(|_| rng.gen_range(0..MAX_QUANTITY))
+                .collect::<Vec<_>>();
+            cuts.sort_unstable();
+            cuts.dedup();
+            almanac += &format!(
+                "\n{}-to-{} map:\n",
+                category_name(layer),
+                category_name(layer + 1)
+            );
+            let mut nb_lines = 0;
+            for (index, segment) in cuts.windows(2).enumerate() {
+                let length = segment[1] - segment[0];
+                if (nb_lines == 0 && index == cuts.len() - 2) || rng.gen_bool(0.7) {
+                    let destination = rng.gen_range(0..MAX_QUANTITY - length);
+                    almanac += &format!("{} {} {}\n", destination, segment[0], length);
+                    nb_lines += 1;
+                }
+            }
+        }
+        almanac
+    }
+
+    fn solution1(&self) -> String {
+        let result = self
+            .start_seeds
+            .iter()
+            .map(|seed| self.convert_quantity(*seed))
+            .collect::<Result<Vec<_>, _>>()
+            .map(|locations| {
+                locations
+                    .into_iter()
+                    .min()
+                    .expect("there is at least one seed")
+            });
+        numeric::answer(result)
+    }
+
+    fn solution2(&self) -> String {
+        numeric::answer(self.lowest_location())
+    }
+}
@@ -0,0 +1,215 @@
+use num::BigInt;
+use rand::Rng;
+use std::io::BufRead;
+use std::io::Read;
+
+use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+use crate::numeric::{self, Number, Overflow, Precision};
+
+type Time = i64;
+type Distance = i64;
+
+pub struct Day6 {
+    /// The digits of the times and the distances, only parsed with the precision of the answers.
+    times: Vec<String>,
+    distances: Vec<String>,
+    precision: Precision,
+}
+
+fn get_inner_interval_size<N: Number>(race_time: &N, race_distance: &N) -> Result<N, Overflow> {
+    // holding the button h milliseconds wins when h * (race_time - h) > race_distance, h lying
+    // between the roots of the quadratic, found exactly with an integer square root
+    let wins = |hold: &N| -> Result<bool, Overflow> {
+        Ok(numeric::mul(hold, &numeric::sub(race_time, hold)?)? > *race_distance)
+    };
+    let discriminant = numeric::sub(
+        &numeric::mul(race_time, race_time)?,
+        &numeric::mul(&N::from(4), race_distance)?,
+    )?;
+    if discriminant <= N::from(0) {
+        panic!("should be a way to beat the course");
+    }
+    let mut shortest_hold = numeric::sub(race_time, &discriminant.sqrt())?.div_floor(&N::from(2));
+    while !wins(&shortest_hold)? {
+        if numeric::mul(&N::from(2), &shortest_hold)? > *race_time {
+            return Ok(N::from(0));
+        }
+        shortest_hold = numeric::add(&shortest_hold, &N::from(1))?;
+    }
+    // the holds are symmetric around half of the race time
+    numeric::add(
+        &numeric::sub(race_time, &numeric::mul(&N::from(2), &shortest_hold)?)?,
+        &N::from(1),
+    )
+}
+
+fn check_races(lines: &[&str]) -> Vec<Problem> {
+    // as big integers, the races of any size being solvable with --bigint
+    let parse_numbers = |line: &str| {
+        line.split_whitespace()
+            .skip(1)
+            .map(|number| number.parse().expect("number checked by the spec"))
+            .collect::<Vec<BigInt>>()
+    };
+    let (times, distances) = (parse_numbers(lines[0]), parse_numbers(lines[1]));
+    if times.len() != distances.len() {
+        return vec![Problem::on_line(
+            2,
+            format!(
+                "expected {} distances, found {}",
+                times.len(),
+                distances.len()
+            ),
+        )];
+    }
+    times
+        .iter()
+        .zip(distances.iter())
+        .enumerate()
+        .filter(|(_, (time, distance))| {
+            let half = *time / 2;
+            &half * (*time - &half) <= **distance
+        })
+        .map(|(index, _)| Problem::on_line(2, format!("race {} cannot be won", index + 1)))
+        .collect()
+}
+
+impl Day for Day6 {
+    fn make_day(input: impl Read) -> Self {
+        let mut data = std::io::BufReader::new(input).lines().map(|line| {
+            line.expect("doc should have lines")
+                .split_once(":")
+                .expect("should have colon")
+                .1
+                .split_whitespace()
+                .map(str::to_string)
+                .collect()
+        });
+        let times = data.next().unwrap();
+        let distances = data.next().unwrap();
+        Day6 {
+            times,
+            distances,
+            precision: Precision::default(),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "bigint" => {
+                self.precision = Precision::from_param(value)?;
+                Ok(())
+            }
+            _ => Err(format!("unknown parameter {}", name)),
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(
+                Block::starting_with("times", r"Time:( +\d+)+")
+                    .then_line("distances", r"Distance:( +\d+)+"),
+            )
+            .check(check_races)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // the second part concatenates every race, which only fits four of them in 64 bits
+        // without --bigint
+        let (times, distances): (Vec<Time>, Vec<Distance>) = (0..size)
+            .map(|_| {
+                let time = rng.gen_range(7..100);
+                let best_distance = (time / 2) * (time - time / 2);
+                (time, rng.gen_range(1..best_distance))
+            })
+            .unzip();
+        let format_numbers = |numbers: &[i64]| {
+            numbers
+                .iter()
+                .map(|number| format!("{:>5}", number))
+                .collect::<String>()
+        };
+        format!(
+            "Time:    {}\nDistance:{}\n",
+            format_numbers(&times),
+            format_numbers(&distances)
+        )
+    }
+
+    fn solution1(&self) -> String {
+        self.try_solution(1)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn solution2(&self) -> String {
+        self.try_solution(2)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_solution(&self, part: u8) -> Result<String, String> {
+        match part {
+            1 => numeric::try_solve(
+                self.precision,
+                || self.multiply_ways(),
+                || self.multiply_ways(),
+            ),
+            _ => numeric::try_solve(
+                self.precision,
+                || self.count_ways_single_race(),
+                || self.count_ways_single_race(),
+            ),
+        }
+    }
+}
+
+impl Day6 {
+    fn multiply_ways<N: Number>(&self) -> Result<N, Overflow> {
+        numeric::product(
+            self.times
+                .iter()
+                .zip(self.distances.iter())
+                .map(|(time, distance)| {
+                    get_inner_interval_size(&numeric::parse(time)?, &numeric::parse(distance)?)
+                }),
+        )
+    }
+
+    fn count_ways_single_race<N: Number>(&self) -> Result<N, Overflow> {
+        let concatenate = |numbers: &[String]| numeric::parse::<N>(&numbers.concat());
+        get_inner_interval_size(&concatenate(&self.times)?, &concatenate(&self.distances)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_inner_interval_size() {
+        assert_eq!(get_inner_interval_size(&7i64, &9), Ok(4));
+        assert_eq!(get_inner_interval_size(&15i64, &40), Ok(8));
+        // the roots 10 and 20 are integers, which only tie the record
+        assert_eq!(get_inner_interval_size(&30i64, &200), Ok(9));
+        assert_eq!(get_inner_interval_size(&3i64, &2), Ok(0));
+    }
+
+    #[test]
+    fn test_bigint() {
+        let input =
+            "Time:      7000000  15000000  30000000\nDistance:  9000000  40000000 200000000\n";
+        let mut day = Day6::make_day(input.as_bytes());
+        let overflow = std::panic::catch_unwind(|| day.solution2());
+        assert!(crate::panic_message(overflow.unwrap_err()).contains("--bigint"));
+        day.set_param("bigint", "true").unwrap();
+        assert_eq!(day.solution2(), "70000001500000029999975");
+
+        // a race beyond 64 bits is only read as big integers
+        let input = "Time:      30000000000\nDistance:  200000000000000000000\n";
+        assert!(crate::days::lint(2023, 6, input).unwrap().is_empty());
+        let mut day = Day6::make_day(input.as_bytes());
+        assert!(day.try_solution(1).unwrap_err().contains("--bigint"));
+        day.set_param("bigint", "true").unwrap();
+        assert_eq!(day.try_solution(1), Ok("9999999999".to_string()));
+    }
+}
@@ -1,9 +1,13 @@
-use std::fs::File;
+use rand::Rng;
+use std::collections::HashSet;
+use std::fmt;
 use std::io::BufRead;
+use std::io::Read;
 use std::iter;
 use std::str::FromStr;
 
 use crate::days::Day;
+use crate::lint::{Block, Spec};
 
 const JOKER: u8 = 11;
 const VALUE_TO_HEX_CLASSIC: [char; 15] = [
@@ -20,7 +24,7 @@ pub struct Day7 {
     hands: Vec<Hand>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct Hand {
     cards: [Card; 5],
     bid: Bid,
@@ -42,6 +46,17 @@ fn char_to_card(c: char) -> Result<Card, ()> {
     }
 }
 
+fn card_to_char(card: Card) -> char {
+    match card {
+        10 => 'T',
+        11 => 'J',
+        12 => 'Q',
+        13 => 'K',
+        14 => 'A',
+        digit => char::from_digit(digit as u32, 10).expect("card should be a digit"),
+    }
+}
+
 fn compute_card_numbers_from_cards_map(cards_map: [usize; 15]) -> (usize, usize) {
     let mut retrieved_values = cards_map
         .into_iter()
@@ -117,6 +132,17 @@ impl FromStr for Hand {
     }
 }
 
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cards = self
+            .cards
+            .iter()
+            .map(|card| card_to_char(*card))
+            .collect::<String>();
+        write!(f, "{} {}", cards, self.bid)
+    }
+}
+
 fn get_hex_power_from_nb_cards(nb_max: usize, nb_second_max: usize) -> char {
     match (nb_max, nb_second_max) {
         (5, 0) => '6',
@@ -131,8 +157,8 @@ fn get_hex_power_from_nb_cards(nb_max: usize, nb_second_max: usize) -> char {
 }
 
 impl Day for Day7 {
-    fn make_day(file: File) -> Self {
-        let hands = std::io::BufReader::new(file)
+    fn make_day(input: impl Read) -> Self {
+        let hands = std::io::BufReader::new(input)
             .lines()
             .map(|line| {
                 line.expect("doc should have lines")
@@ -143,6 +169,27 @@ impl Day for Day7 {
         Day7 { hands }
     }
 
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::lines("hand", r"[2-9TJQKA]{5} \d+"))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        const CARD_CHARS: &[u8] = b"23456789TJQKA";
+        // hands are distinct, so there cannot be more of them than possible hands
+        let nb_hands = size.min(CARD_CHARS.len().pow(5));
+        let mut seen_hands = HashSet::with_capacity(nb_hands);
+        let mut hands = String::with_capacity(nb_hands * 10);
+        while seen_hands.len() < nb_hands {
+            let cards = (0..5)
+                .map(|_| char::from(CARD_CHARS[rng.gen_range(0..CARD_CHARS.len())]))
+                .collect::<String>();
+            if seen_hands.insert(cards.clone()) {
+                hands += &format!("{} {}\n", cards, rng.gen_range(1..=1000));
+            }
+        }
+        hands
+    }
+
     fn solution1(&self) -> String {
         let mut hands_sorted = self
             .hands
@@ -177,18 +224,10 @@ impl Day for Day7 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::days::check_round_trip;
 
     #[test]
-    fn test_day7_sol1() {
-        let input = File::open("./inputs/day7/input_test.txt").expect("File not found");
-        let day = Day7::make_day(input);
-        assert_eq!(day.solution1(), "6440");
-    }
-
-    #[test]
-    fn test_day7_sol2() {
-        let input = File::open("./inputs/day7/input_test.txt").expect("File not found");
-        let day = Day7::make_day(input);
-        assert_eq!(day.solution2(), "5905");
+    fn test_display_round_trip() {
+        check_round_trip::<Day7, Hand>(10, true, |line| line.parse().ok());
     }
 }
@@ -0,0 +1,274 @@
+use num::integer::lcm;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use std::fmt;
+use std::io::BufRead;
+use std::io::Read;
+use std::str::FromStr;
+
+use crate::days::Day;
+use crate::lint::{Block, Problem, Spec};
+
+#[derive(Debug)]
+pub struct Day8 {
+    instructions: Vec<Direction>,
+    nodes: HashMap<String, Node>,
+}
+
+#[derive(Debug)]
+pub struct Walker<'a> {
+    curr_node_id: &'a str,
+    nb_instructions: usize,
+}
+
+impl<'a> Walker<'a> {
+    fn make_from_id(id: &'a str) -> Walker<'a> {
+        Walker {
+            curr_node_id: id,
+            nb_instructions: 0,
+        }
+    }
+
+    fn walk<F>(mut self, day8: &'a Day8, end_condition: F) -> usize
+    where
+        F: Fn(&str) -> bool,
+    {
+        let length = day8.instructions.len();
+        while !end_condition(self.curr_node_id) {
+            self.curr_node_id = match day8.instructions[self.nb_instructions % length] {
+                Direction::Left => &day8.nodes[self.curr_node_id].left_node_id,
+                Direction::Right => &day8.nodes[self.curr_node_id].right_node_id,
+            };
+            self.nb_instructions += 1;
+        }
+        self.nb_instructions
+    }
+}
+#[derive(Debug)]
+enum Direction {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Direction {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            'L' => Ok(Direction::Left),
+            'R' => Ok(Direction::Right),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Node {
+    id: String,
+    left_node_id: String,
+    right_node_id: String,
+}
+
+impl FromStr for Node {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (id, follow_up_str) = s.split_once(" = ").ok_or(())?;
+        let pattern: &[_] = &['(', ')'];
+        let (left_node_id, right_node_id) = follow_up_str
+            .trim_matches(pattern)
+            .split_once(", ")
+            .ok_or(())?;
+        let id = id.to_owned();
+        let left_node_id = left_node_id.to_owned();
+        let right_node_id = right_node_id.to_owned();
+        Ok(Node {
+            id,
+            left_node_id,
+            right_node_id,
+        })
+    }
+}
+
+impl fmt::Display for Node {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} = ({}, {})",
+            self.id, self.left_node_id, self.right_node_id
+        )
+    }
+}
+
+fn check_network(lines: &[&str]) -> Vec<Problem> {
+    let nodes = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| Some((index + 1, line.parse::<Node>().ok()?)))
+        .collect::<Vec<_>>();
+    let mut problems = vec![];
+    let mut definitions = HashMap::with_capacity(nodes.len());
+    for (line, node) in nodes.iter() {
+        if let Some(first_line) = definitions.insert(node.id.as_str(), *line) {
+            problems.push(Problem::on_line(
+                *line,
+                format!("node {} already defined on line {}", node.id, first_line),
+            ));
+        }
+    }
+    for (line, node) in nodes.iter() {
+        for id in [&node.left_node_id, &node.right_node_id] {
+            if !definitions.contains_key(id.as_str()) {
+                problems.push(Problem::on_line(*line, format!("unknown node {}", id)));
+            }
+        }
+    }
+    problems
+}
+
+impl Day for Day8 {
+    fn make_day(input: impl Read) -> Self {
+        let document_lines = std::io::BufReader::new(input)
+            .lines()
+            .map(|line| line.expect("doc should have lines"))
+            .collect::<Vec<_>>();
+
+        let instructions = document_lines[0]
+            .chars()
+            .map(|dir_ch| dir_ch.try_into().expect("should be L or R"))
+            .collect();
+
+        let nodes = document_lines
+            .into_iter()
+            .skip(2)
+            .map(|line_node| {
+                let node = line_node.parse::<Node>().expect("should be node line");
+                (node.id.clone(), node)
+            })
+            .collect();
+
+        Day8 {
+            instructions,
+            nodes,
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default()
+            .block(Block::starting_with("instructions", "[LR]+"))
+            .block(Block::lines("node", r"\w+ = \(\w+, \w+\)"))
+            .check(check_network)
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // Each ghost walks a chain of nodes whose length is a multiple of the number of
+        // instructions, so that leaving its Z node brings it back on the same path.
+        const PERIOD_FACTORS: [usize; 6] = [2, 3, 5, 7, 11, 13];
+        let nb_instructions = size.max(1);
+        let instructions = (0..nb_instructions)
+            .map(|_| {
+                if rng.gen_bool(0.5) {
+                    Direction::Left
+                } else {
+                    Direction::Right
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut intermediate_names = (0..).map(|index: usize| {
+            // never ends with A or Z, unlike start and end nodes
+            let mut name = char::from(b'B' + (index % 24) as u8).to_string();
+            let mut rest = index / 24;
+            while name.len() < 3 || rest > 0 {
+                name.insert(0, char::from(b'A' + (rest % 26) as u8));
+                rest /= 26;
+            }
+            name
+        });
+
+        let mut chains = vec![];
+        for (ghost, factor) in PERIOD_FACTORS[..rng.gen_range(1..=6)].iter().enumerate() {
+            let (start_name, end_name) = if ghost == 0 {
+                ("AAA".to_string(), "ZZZ".to_string())
+            } else {
+                let letter = char::from(b'A' + rng.gen_range(0..26));
+                (
+                    format!("{}{}A", ghost, letter),
+                    format!("{}{}Z", ghost, letter),
+                )
+            };
+            let chain_length = nb_instructions * factor;
+            let mut chain = vec![start_name];
+            chain.extend(intermediate_names.by_ref().take(chain_length - 1));
+            chain.push(end_name);
+            chains.push(chain);
+        }
+        let all_names = chains.iter().flatten().cloned().collect::<Vec<_>>();
+
+        let mut node_lines = vec![];
+        for chain in chains.iter() {
+            for (step, name) in chain.iter().enumerate() {
+                let next_name = if step + 1 < chain.len() {
+                    &chain[step + 1]
+                } else {
+                    &chain[1]
+                };
+                let other_name = &all_names[rng.gen_range(0..all_names.len())];
+                let (left_node_id, right_node_id) = match instructions[step % nb_instructions] {
+                    Direction::Left => (next_name, other_name),
+                    Direction::Right => (other_name, next_name),
+                };
+                let node = Node {
+                    id: name.clone(),
+                    left_node_id: left_node_id.clone(),
+                    right_node_id: right_node_id.clone(),
+                };
+                node_lines.push(node.to_string());
+            }
+        }
+        node_lines.shuffle(rng);
+
+        let instructions = instructions
+            .iter()
+            .map(|direction| match direction {
+                Direction::Left => 'L',
+                Direction::Right => 'R',
+            })
+            .collect::<String>();
+        format!("{}\n\n{}\n", instructions, node_lines.join("\n"))
+    }
+
+    fn solution1(&self) -> String {
+        let walker = Walker::make_from_id("AAA");
+        let result = walker.walk(self, |id| id == "ZZZ");
+        result.to_string()
+    }
+
+    fn solution2(&self) -> String {
+        let walkers = self
+            .nodes
+            .keys()
+            .filter(|node_id| node_id.chars().last().is_some_and(|c| c == 'A'))
+            .map(|node_id| Walker::make_from_id(node_id))
+            .collect::<Vec<_>>();
+        let time_to_reach = walkers
+            .into_iter()
+            .map(|walker| walker.walk(self, |id| id.chars().last().is_some_and(|c| c == 'Z')))
+            .reduce(lcm)
+            .expect("there is at least one walker");
+        time_to_reach.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::check_round_trip;
+
+    #[test]
+    fn test_display_round_trip() {
+        check_round_trip::<Day8, Node>(10, true, |line| {
+            line.contains('=').then(|| line.parse().unwrap())
+        });
+    }
+}
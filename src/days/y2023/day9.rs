@@ -0,0 +1,182 @@
+use rand::Rng;
+use std::io::BufRead;
+use std::io::Read;
+
+use crate::days::Day;
+use crate::lint::{Block, Spec};
+use crate::numeric::{self, Number, Overflow, Precision};
+
+type Value = i64;
+
+pub struct Day9 {
+    data: Vec<Sequence>,
+    precision: Precision,
+}
+
+struct Sequence {
+    /// The digits of the values, only parsed with the precision of the answers.
+    values: Vec<String>,
+}
+
+impl Sequence {
+    fn parse(line: &str) -> Sequence {
+        Sequence {
+            values: line.split_whitespace().map(str::to_string).collect(),
+        }
+    }
+
+    /// The value after the last one, the sum of the last values of the differences of every order,
+    /// each difference being checked so that only answers out of 64 bits overflow.
+    fn compute_next_value<N: Number>(&self) -> Result<N, Overflow> {
+        let mut differences = self
+            .values
+            .iter()
+            .map(|value| numeric::parse::<N>(value))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut next_value = N::from(0);
+        while differences
+            .iter()
+            .any(|difference| *difference != N::from(0))
+        {
+            next_value = numeric::add(&next_value, differences.last().unwrap())?;
+            differences = differences
+                .windows(2)
+                .map(|pair| numeric::sub(&pair[1], &pair[0]))
+                .collect::<Result<_, _>>()?;
+        }
+        Ok(next_value)
+    }
+
+    fn reverse(&self) -> Self {
+        let mut values_reverse = self.values.clone();
+        values_reverse.reverse();
+        Self {
+            values: values_reverse,
+        }
+    }
+}
+
+impl Day9 {
+    fn sum_next_values<N: Number>(&self, backwards: bool) -> Result<N, Overflow> {
+        numeric::sum(self.data.iter().map(|seq| match backwards {
+            true => seq.reverse().compute_next_value(),
+            false => seq.compute_next_value(),
+        }))
+    }
+}
+
+impl Day for Day9 {
+    fn make_day(input: impl Read) -> Self {
+        let data: Vec<Sequence> = std::io::BufReader::new(input)
+            .lines()
+            .map(|line| Sequence::parse(&line.expect("doc should have lines")))
+            .collect();
+        Day9 {
+            data,
+            precision: Precision::default(),
+        }
+    }
+
+    fn set_param(&mut self, name: &str, value: &str) -> Result<(), String> {
+        match name {
+            "bigint" => {
+                self.precision = Precision::from_param(value)?;
+                Ok(())
+            }
+            _ => Err(format!("unknown parameter {}", name)),
+        }
+    }
+
+    fn input_spec() -> Spec {
+        Spec::default().block(Block::lines("sequence", r"-?\d+( -?\d+)*"))
+    }
+
+    fn generate_input(rng: &mut impl Rng, size: usize) -> String {
+        // short sequences of small polynomials
+        (0..size)
+            .map(|_| {
+                let coefficients = (0..=rng.gen_range(0..=4))
+                    .map(|_| rng.gen_range(-3..=3))
+                    .collect::<Vec<Value>>();
+                (0..rng.gen_range(5..=12))
+                    .map(|x: Value| {
+                        coefficients
+                            .iter()
+                            .rev()
+                            .fold(0, |value, coefficient| value * x + coefficient)
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    + "\n"
+            })
+            .collect()
+    }
+
+    fn solution1(&self) -> String {
+        self.try_solution(1)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn solution2(&self) -> String {
+        self.try_solution(2)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    fn try_solution(&self, part: u8) -> Result<String, String> {
+        let backwards = part == 2;
+        numeric::try_solve(
+            self.precision,
+            || self.sum_next_values(backwards),
+            || self.sum_next_values(backwards),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compute_next_value() {
+        let seq = Sequence::parse("1 3 6 10 15 21");
+        assert_eq!(seq.compute_next_value::<i64>(), Ok(28));
+    }
+
+    #[test]
+    fn test_compute_previous_value() {
+        let seq = Sequence::parse("10 13 16 21 30 45").reverse();
+        assert_eq!(seq.compute_next_value::<i64>(), Ok(5));
+    }
+
+    #[test]
+    fn test_long_sequence() {
+        // long sequences with small differences fit in 64 bits
+        let input = (0..70).map(|n| n.to_string()).collect::<Vec<_>>().join(" ") + "\n";
+        let day = Day9::make_day(input.as_bytes());
+        assert_eq!(day.solution1(), "70");
+        assert_eq!(day.solution2(), "-1");
+    }
+
+    #[test]
+    fn test_bigint() {
+        let input = format!("{} {}\n", i64::MAX - 1, i64::MAX);
+        let mut day = Day9::make_day(input.as_bytes());
+        let overflow = std::panic::catch_unwind(|| day.solution1());
+        assert!(crate::panic_message(overflow.unwrap_err()).contains("--bigint"));
+        assert_eq!(day.solution2(), (i64::MAX - 2).to_string());
+        day.set_param("bigint", "true").unwrap();
+        assert_eq!(day.solution1(), (i64::MAX as u64 + 1).to_string());
+
+        // values beyond 64 bits are only read as big integers
+        let input = format!("{} {}\n", i64::MAX as u64 + 1, i64::MAX as u64 + 3);
+        let mut day = Day9::make_day(input.as_bytes());
+        assert_eq!(
+            day.try_solution(1),
+            Err("overflow of a 64-bit integer, run with --bigint for an exact answer".to_string())
+        );
+        day.set_param("bigint", "true").unwrap();
+        assert_eq!(day.try_solution(1), Ok((i64::MAX as u64 + 5).to_string()));
+        assert_eq!(day.try_solution(2), Ok((i64::MAX as u64 - 1).to_string()));
+    }
+}
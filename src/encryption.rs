@@ -0,0 +1,140 @@
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use std::path::{Path, PathBuf};
+
+// the names the variables had when the crate only did 2023 are still read after the new ones
+const KEY_VARS: [&str; 2] = ["AOC_INPUT_KEY", "AOC23_INPUT_KEY"];
+const KEY_FILE_VARS: [&str; 2] = ["AOC_INPUT_KEY_FILE", "AOC23_INPUT_KEY_FILE"];
+const DEFAULT_KEY_FILE: &str = "./.input_key";
+// kept from the 2023-only crate on purpose, renaming it would make the committed inputs unreadable
+const MAGIC: &[u8] = b"AOC23ENC";
+const NONCE_SIZE: usize = 12;
+pub const EXTENSION: &str = "enc";
+
+fn first_var(names: &[&str]) -> Option<String> {
+    names.iter().find_map(|name| std::env::var(name).ok())
+}
+
+/// Reads the 32 bytes key, written in hex in `AOC_INPUT_KEY` or in the key file.
+pub fn load_key() -> Result<Key, String> {
+    let hex_key = match first_var(&KEY_VARS) {
+        Some(hex_key) => hex_key,
+        None => {
+            let path = first_var(&KEY_FILE_VARS).unwrap_or(DEFAULT_KEY_FILE.to_string());
+            std::fs::read_to_string(&path).map_err(|_| {
+                format!(
+                    "no key to decrypt inputs, set {} or write one to {}",
+                    KEY_VARS[0], path
+                )
+            })?
+        }
+    };
+    parse_key(hex_key.trim())
+}
+
+fn parse_key(hex_key: &str) -> Result<Key, String> {
+    if hex_key.len() != 64 || !hex_key.is_ascii() {
+        return Err("key should be 64 hexadecimal digits".to_string());
+    }
+    let bytes = (0..32)
+        .map(|idx| u8::from_str_radix(&hex_key[2 * idx..2 * idx + 2], 16))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| "key should be 64 hexadecimal digits")?;
+    Ok(*Key::from_slice(&bytes))
+}
+
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Vec<u8> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext)
+        .expect("encryption should not fail");
+    [MAGIC, nonce.as_slice(), &ciphertext].concat()
+}
+
+pub fn decrypt(key: &Key, encrypted: &[u8]) -> Result<Vec<u8>, String> {
+    let encrypted = encrypted
+        .strip_prefix(MAGIC)
+        .filter(|encrypted| encrypted.len() >= NONCE_SIZE)
+        .ok_or("not an encrypted input")?;
+    let (nonce, ciphertext) = encrypted.split_at(NONCE_SIZE);
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| "wrong key or corrupted input".to_string())
+}
+
+pub fn encrypted_path(path: &Path) -> PathBuf {
+    let mut encrypted_path = path.as_os_str().to_owned();
+    encrypted_path.push(".");
+    encrypted_path.push(EXTENSION);
+    PathBuf::from(encrypted_path)
+}
+
+/// Whether only the encrypted version of an input is there, so that reading it needs the key.
+pub fn needs_key(path: &Path) -> bool {
+    !path.exists() && encrypted_path(path).exists()
+}
+
+/// Reads an input file, or decrypts in memory its encrypted version when only that one exists.
+pub fn read_input_file(path: &Path) -> Result<String, String> {
+    if !needs_key(path) {
+        return std::fs::read_to_string(path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e));
+    }
+    let encrypted_path = encrypted_path(path);
+    let encrypted = std::fs::read(&encrypted_path)
+        .map_err(|e| format!("cannot read {}: {}", encrypted_path.display(), e))?;
+    let plaintext = decrypt(&load_key()?, &encrypted)
+        .map_err(|e| format!("cannot decrypt {}: {}", encrypted_path.display(), e))?;
+    String::from_utf8(plaintext).map_err(|_| format!("{} is not text", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEX_KEY: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    #[test]
+    fn test_round_trip() {
+        let key = parse_key(HEX_KEY).unwrap();
+        let input = b"32T3K 765\nT55J5 684\n";
+        let encrypted = encrypt(&key, input);
+        assert!(encrypted.starts_with(MAGIC));
+        assert_eq!(decrypt(&key, &encrypted).unwrap(), input);
+        // a fresh nonce each time
+        assert_ne!(encrypt(&key, input), encrypted);
+    }
+
+    #[test]
+    fn test_decrypt_errors() {
+        let key = parse_key(HEX_KEY).unwrap();
+        let mut encrypted = encrypt(&key, b"rn=1,cm-\n");
+        let other_key = parse_key(&HEX_KEY.replace('0', "f")).unwrap();
+        assert!(decrypt(&other_key, &encrypted).is_err());
+        *encrypted.last_mut().unwrap() ^= 1;
+        assert!(decrypt(&key, &encrypted).is_err());
+        assert!(decrypt(&key, b"rn=1,cm-\n").is_err());
+    }
+
+    #[test]
+    fn test_first_var() {
+        std::env::set_var("AOC_TEST_FIRST_VAR_OLD", "old");
+        assert_eq!(
+            first_var(&["AOC_TEST_FIRST_VAR_NEW", "AOC_TEST_FIRST_VAR_OLD"]),
+            Some("old".to_string())
+        );
+        std::env::set_var("AOC_TEST_FIRST_VAR_NEW", "new");
+        assert_eq!(
+            first_var(&["AOC_TEST_FIRST_VAR_NEW", "AOC_TEST_FIRST_VAR_OLD"]),
+            Some("new".to_string())
+        );
+        assert_eq!(first_var(&["AOC_TEST_FIRST_VAR_UNSET"]), None);
+    }
+
+    #[test]
+    fn test_parse_key() {
+        assert!(parse_key(HEX_KEY).is_ok());
+        assert!(parse_key(&HEX_KEY[2..]).is_err());
+        assert!(parse_key(&HEX_KEY.replace('a', "g")).is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::days::{self, Day};
+use crate::encryption;
+
+pub const FIXTURES_DIR: &str = "./fixtures";
+const MANIFEST_FILE: &str = "examples.toml";
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Manifest {
+    #[serde(rename = "example", default)]
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    pub fn load() -> Result<Manifest, String> {
+        let path = Path::new(FIXTURES_DIR).join(MANIFEST_FILE);
+        let contents = std::fs::read_to_string(&path)
+            .map_err(|e| format!("cannot read {}: {}", path.display(), e))?;
+        toml::from_str(&contents).map_err(|e| format!("invalid {}: {}", path.display(), e))
+    }
+
+    /// The example reading the puzzle input at `path`, if the manifest lists it.
+    pub fn find_input(&self, path: &Path) -> Option<&Example> {
+        let path = comparable_path(path)?;
+        self.examples.iter().find(|example| {
+            example
+                .input_path()
+                .is_some_and(|input_path| comparable_path(&input_path).as_ref() == Some(&path))
+        })
+    }
+
+    #[cfg(test)]
+    pub fn find(&self, year: u32, day: u32, name: &str) -> Option<&Example> {
+        self.examples
+            .iter()
+            .find(|example| example.year == year && example.day == day && example.name == name)
+    }
+}
+
+/// The path with its directory resolved, the file itself possibly being only encrypted.
+fn comparable_path(path: &Path) -> Option<PathBuf> {
+    Some(path.parent()?.canonicalize().ok()?.join(path.file_name()?))
+}
+
+/// Examples written before the manifest covered several years are from 2023.
+fn default_year() -> u32 {
+    2023
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Example {
+    #[serde(default = "default_year")]
+    pub year: u32,
+    pub day: u32,
+    pub name: String,
+    input: Option<String>,
+    path: Option<PathBuf>,
+    #[serde(default)]
+    params: HashMap<String, toml::Value>,
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+impl Example {
+    pub fn read_input(&self) -> Result<String, String> {
+        match (&self.input, &self.path) {
+            (Some(input), None) => Ok(input.clone()),
+            (None, Some(path)) => encryption::read_input_file(&Path::new(FIXTURES_DIR).join(path)),
+            _ => Err(format!(
+                "example {} of day {} needs exactly one of input and path",
+                self.name, self.day
+            )),
+        }
+    }
+
+    /// Where the input is read from, when it is not inline.
+    pub fn input_path(&self) -> Option<PathBuf> {
+        self.path
+            .as_ref()
+            .map(|path| Path::new(FIXTURES_DIR).join(path))
+    }
+
+    /// Whether the input is only stored encrypted, and so cannot be read without the key.
+    #[cfg(test)]
+    pub fn needs_key(&self) -> bool {
+        self.path
+            .as_ref()
+            .is_some_and(|path| encryption::needs_key(&Path::new(FIXTURES_DIR).join(path)))
+    }
+
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn make_day(&self) -> Result<Box<dyn Day>, String> {
+        self.make_day_from(&self.read_input()?)
+    }
+
+    /// Builds the day from another version of the example input, with the example's params.
+    pub fn make_day_from(&self, input: &str) -> Result<Box<dyn Day>, String> {
+        let mut day = days::make_day(self.year, self.day, input.as_bytes())
+            .ok_or_else(|| format!("day {} of {} is not registered", self.day, self.year))?;
+        for (name, value) in self.params.iter() {
+            let value = match value {
+                toml::Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            day.set_param(name, &value)?;
+        }
+        Ok(day)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_example(year: u32, day: u32, name: &str, part: u8) {
+        let manifest = Manifest::load().unwrap();
+        let example = manifest.find(year, day, name).expect("example in manifest");
+        // examples needing the key are ignored unless asked for, and then fail without it
+        let solved = example
+            .make_day()
+            .unwrap_or_else(|error| panic!("{}", error));
+        let result = match part {
+            1 => solved.solution1(),
+            _ => solved.solution2(),
+        };
+        assert_eq!(Some(result.as_str()), example.expected(part));
+    }
+
+    #[test]
+    fn test_examples_pass_lint() {
+        let manifest = Manifest::load().unwrap();
+        for example in manifest.examples.iter() {
+            if example.needs_key() && encryption::load_key().is_err() {
+                continue;
+            }
+            let input = example.read_input().unwrap();
+            let problems = days::lint(example.year, example.day, &input).unwrap();
+            assert!(problems.is_empty(), "{}: {:?}", example.name, problems);
+        }
+    }
+
+    #[test]
+    fn test_examples_saved_on_windows() {
+        let manifest = Manifest::load().unwrap();
+        for example in manifest.examples.iter() {
+            if example.needs_key() && encryption::load_key().is_err() {
+                continue;
+            }
+            let input = example.read_input().unwrap();
+            let windows_input = format!("\u{feff}{}\r\n", input.replace('\n', "\r\n"));
+            let solved = example.make_day_from(&windows_input).unwrap();
+            if let Some(expected) = example.expected(1) {
+                assert_eq!(solved.solution1(), expected, "{}", example.name);
+            }
+            if let Some(expected) = example.expected(2) {
+                assert_eq!(solved.solution2(), expected, "{}", example.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_encrypted_examples() {
+        let generated = include_str!(concat!(env!("OUT_DIR"), "/fixture_tests.rs"));
+        for example in Manifest::load().unwrap().examples.iter() {
+            let call = format!(
+                "check_example({}, {}, {:?}, ",
+                example.year, example.day, example.name
+            );
+            for test_code in generated.split("\n\n").filter(|code| code.contains(&call)) {
+                assert_eq!(
+                    test_code.contains("#[ignore"),
+                    example.needs_key(),
+                    "{}",
+                    test_code
+                );
+            }
+        }
+
+        let directory = std::env::temp_dir().join(format!("aoc-fixtures-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("input.txt");
+        let manifest: Manifest = toml::from_str(&format!(
+            "[[example]]\nday = 6\nname = \"encrypted\"\npath = {:?}\npart1 = \"288\"\n",
+            path
+        ))
+        .unwrap();
+        let example = &manifest.examples[0];
+        let input = "Time:      7  15   30\nDistance:  9  40  200\n";
+        let key = encryption::load_key();
+        let encryption_key = match &key {
+            Ok(key) => *key,
+            Err(_) => chacha20poly1305::Key::from([7; 32]),
+        };
+        let encrypted = encryption::encrypt(&encryption_key, input.as_bytes());
+        std::fs::write(encryption::encrypted_path(&path), encrypted).unwrap();
+        assert!(example.needs_key());
+        match key {
+            Ok(_) => assert_eq!(example.make_day().unwrap().solution1(), "288"),
+            Err(error) => assert_eq!(example.read_input(), Err(error)),
+        }
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+
+    // one test per example and part, generated by build.rs from the manifest
+    include!(concat!(env!("OUT_DIR"), "/fixture_tests.rs"));
+}
@@ -0,0 +1,344 @@
+use regex::Regex;
+use std::fmt;
+
+/// Something wrong in an input, on a line counted from 1 or in the input as a whole.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn on_line(line: usize, message: impl Into<String>) -> Problem {
+        Problem {
+            line: Some(line),
+            message: message.into(),
+        }
+    }
+
+    pub fn on_input(message: impl Into<String>) -> Problem {
+        Problem {
+            line: None,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.line {
+            Some(line) => write!(f, "line {}: {}", line, self.message),
+            None => write!(f, "input: {}", self.message),
+        }
+    }
+}
+
+/// A kind of line, named in the problems reported about it.
+struct LinePattern {
+    name: &'static str,
+    regex: Regex,
+}
+
+impl LinePattern {
+    fn new(name: &'static str, regex: &str) -> LinePattern {
+        let regex = Regex::new(&format!("^(?:{})$", regex)).expect("pattern should be a regex");
+        LinePattern { name, regex }
+    }
+}
+
+enum BlockShape {
+    Lines {
+        first: Vec<LinePattern>,
+        rest: Option<LinePattern>,
+    },
+    Grid {
+        name: &'static str,
+        cell: Regex,
+    },
+}
+
+/// Lines of an input between blank lines.
+pub struct Block {
+    shape: BlockShape,
+}
+
+impl Block {
+    /// Any number of lines, each one matching `regex`.
+    pub fn lines(name: &'static str, regex: &str) -> Block {
+        Block {
+            shape: BlockShape::Lines {
+                first: vec![],
+                rest: Some(LinePattern::new(name, regex)),
+            },
+        }
+    }
+
+    /// A first line matching `regex`, to be followed by the lines given by `then_*`.
+    pub fn starting_with(name: &'static str, regex: &str) -> Block {
+        Block {
+            shape: BlockShape::Lines {
+                first: vec![LinePattern::new(name, regex)],
+                rest: None,
+            },
+        }
+    }
+
+    pub fn then_line(mut self, name: &'static str, regex: &str) -> Block {
+        if let BlockShape::Lines { first, .. } = &mut self.shape {
+            first.push(LinePattern::new(name, regex));
+        }
+        self
+    }
+
+    pub fn then_lines(mut self, name: &'static str, regex: &str) -> Block {
+        if let BlockShape::Lines { rest, .. } = &mut self.shape {
+            *rest = Some(LinePattern::new(name, regex));
+        }
+        self
+    }
+
+    /// A rectangle of characters, each one matching the class `cell`.
+    pub fn grid(name: &'static str, cell: &str) -> Block {
+        let cell = Regex::new(&format!("^(?:{})$", cell)).expect("cell should be a regex");
+        Block {
+            shape: BlockShape::Grid { name, cell },
+        }
+    }
+
+    fn lint(&self, first_line: usize, lines: &[&str], problems: &mut Vec<Problem>) {
+        match &self.shape {
+            BlockShape::Lines { first, rest } => {
+                for (index, line) in lines.iter().enumerate() {
+                    let line_number = first_line + index;
+                    match first.get(index).or(rest.as_ref()) {
+                        Some(pattern) if pattern.regex.is_match(line) => {}
+                        Some(pattern) => {
+                            problems.push(Problem::on_line(
+                                line_number,
+                                format!("expected {}", pattern.name),
+                            ));
+                        }
+                        None => problems.push(Problem::on_line(line_number, "unexpected line")),
+                    }
+                }
+                for pattern in first.iter().skip(lines.len()) {
+                    problems.push(Problem::on_line(
+                        first_line + lines.len() - 1,
+                        format!("missing {} after this line", pattern.name),
+                    ));
+                }
+            }
+            BlockShape::Grid { name, cell } => {
+                let width = lines[0].chars().count();
+                for (index, line) in lines.iter().enumerate() {
+                    let line_number = first_line + index;
+                    let line_width = line.chars().count();
+                    if line_width != width {
+                        problems.push(Problem::on_line(
+                            line_number,
+                            format!(
+                                "expected {} cells as on the first line, found {}",
+                                width, line_width
+                            ),
+                        ));
+                    }
+                    let mut buffer = [0; 4];
+                    if let Some((column, c)) = line
+                        .chars()
+                        .enumerate()
+                        .find(|(_, c)| !cell.is_match(c.encode_utf8(&mut buffer)))
+                    {
+                        problems.push(Problem::on_line(
+                            line_number,
+                            format!(
+                                "column {}: {:?} is not a cell of the {}",
+                                column + 1,
+                                c,
+                                name
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub type Check = fn(&[&str]) -> Vec<Problem>;
+
+/// The expected shape of a day's input, as blocks separated by blank lines, and the checks of its
+/// meaning, which only run on a well shaped input.
+#[derive(Default)]
+pub struct Spec {
+    blocks: Vec<Block>,
+    repeat_last_block: bool,
+    counts: Vec<(char, usize)>,
+    checks: Vec<Check>,
+}
+
+impl Spec {
+    pub fn block(mut self, block: Block) -> Spec {
+        self.blocks.push(block);
+        self
+    }
+
+    /// Accepts any number of blocks shaped like the last one.
+    pub fn repeat_last_block(mut self) -> Spec {
+        self.repeat_last_block = true;
+        self
+    }
+
+    /// Expects `c` exactly `count` times in the whole input.
+    pub fn count(mut self, c: char, count: usize) -> Spec {
+        self.counts.push((c, count));
+        self
+    }
+
+    pub fn check(mut self, check: Check) -> Spec {
+        self.checks.push(check);
+        self
+    }
+
+    /// Whether some block of the input is a grid, that can be shown as is.
+    pub fn has_grid(&self) -> bool {
+        self.blocks
+            .iter()
+            .any(|block| matches!(block.shape, BlockShape::Grid { .. }))
+    }
+
+    pub fn lint(&self, input: &str) -> Vec<Problem> {
+        let lines = input.lines().collect::<Vec<_>>();
+        if lines.is_empty() {
+            return vec![Problem::on_input("empty input")];
+        }
+
+        let mut problems = vec![];
+        let blocks = split_blocks(&lines);
+        if blocks.len() < self.blocks.len() {
+            problems.push(Problem::on_input(format!(
+                "expected {} blocks separated by blank lines, found {}",
+                self.blocks.len(),
+                blocks.len()
+            )));
+        }
+        for (index, (first_line, block_lines)) in blocks.iter().enumerate() {
+            let block = match self.blocks.get(index) {
+                Some(block) => block,
+                None if self.repeat_last_block => self.blocks.last().unwrap(),
+                None => {
+                    problems.push(Problem::on_line(*first_line, "unexpected block"));
+                    continue;
+                }
+            };
+            block.lint(*first_line, block_lines, &mut problems);
+        }
+
+        for (c, count) in self.counts.iter() {
+            let mut occurrences = lines
+                .iter()
+                .enumerate()
+                .flat_map(|(index, line)| line.matches(*c).map(move |_| index + 1));
+            match occurrences.nth(*count) {
+                Some(line) => problems.push(Problem::on_line(
+                    line,
+                    format!("more than {} {:?}", count, c),
+                )),
+                None if input.matches(*c).count() < *count => {
+                    problems.push(Problem::on_input(format!(
+                        "expected {} {:?}, found {}",
+                        count,
+                        c,
+                        input.matches(*c).count()
+                    )))
+                }
+                None => {}
+            }
+        }
+
+        if problems.is_empty() {
+            for check in self.checks.iter() {
+                problems.extend(check(&lines));
+            }
+        }
+        problems.sort_by_key(|problem| problem.line);
+        problems
+    }
+}
+
+/// Splits lines at blank lines, keeping the number of the first line of each block.
+fn split_blocks<'a, 'b>(lines: &'b [&'a str]) -> Vec<(usize, &'b [&'a str])> {
+    let mut blocks = vec![];
+    let mut first_index = 0;
+    for (index, line) in lines.iter().enumerate() {
+        if line.is_empty() {
+            if index > first_index {
+                blocks.push((first_index + 1, &lines[first_index..index]));
+            }
+            first_index = index + 1;
+        }
+    }
+    if lines.len() > first_index {
+        blocks.push((first_index + 1, &lines[first_index..]));
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(problems: Vec<Problem>) -> Vec<String> {
+        problems.iter().map(|problem| problem.to_string()).collect()
+    }
+
+    #[test]
+    fn test_lint_lines() {
+        let spec = Spec::default()
+            .block(Block::starting_with("seeds", r"seeds:( \d+)+"))
+            .block(
+                Block::starting_with("map header", r"[a-z]+-to-[a-z]+ map:")
+                    .then_lines("range", r"\d+ \d+ \d+"),
+            )
+            .repeat_last_block();
+        assert!(spec
+            .lint("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n\nsoil-to-water map:\n0 15 37\n")
+            .is_empty());
+        assert_eq!(
+            messages(spec.lint("seeds: 79 14\nseed-to-soil map:\n\n50 98\n")),
+            ["line 2: unexpected line", "line 4: expected map header",]
+        );
+        assert_eq!(
+            messages(spec.lint("seeds: 79 14\n")),
+            ["input: expected 2 blocks separated by blank lines, found 1"]
+        );
+    }
+
+    #[test]
+    fn test_lint_grid() {
+        let spec = Spec::default()
+            .block(Block::grid("garden", "[.#S]"))
+            .count('S', 1);
+        assert!(spec.lint("...\n.S.\n.#.\n").is_empty());
+        assert_eq!(
+            messages(spec.lint("...\n.S.S\n.x.\n")),
+            [
+                "line 2: expected 3 cells as on the first line, found 4",
+                "line 2: more than 1 'S'",
+                "line 3: column 2: 'x' is not a cell of the garden",
+            ]
+        );
+        assert_eq!(
+            messages(spec.lint("...\n")),
+            ["input: expected 1 'S', found 0"]
+        );
+    }
+
+    #[test]
+    fn test_checks_run_on_well_shaped_inputs() {
+        let spec = Spec::default()
+            .block(Block::lines("number", r"\d+"))
+            .check(|lines| vec![Problem::on_input(format!("{} lines", lines.len()))]);
+        assert_eq!(messages(spec.lint("1\n2\n")), ["input: 2 lines"]);
+        assert_eq!(messages(spec.lint("1\nb\n")), ["line 2: expected number"]);
+    }
+}
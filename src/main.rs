@@ -1,127 +1,467 @@
-use crate::days::{
-    day1::Day1, day10::Day10, day11::Day11, day12::Day12, day13::Day13, day14::Day14, day15::Day15,
-    day16::Day16, day17::Day17, day18::Day18, day19::Day19, day2::Day2, day20::Day20, day21::Day21,
-    day3::Day3, day4::Day4, day5::Day5, day6::Day6, day7::Day7, day8::Day8, day9::Day9, Day,
-};
 use std::{
+    any::Any,
     fs::File,
-    time::{Duration, Instant},
+    io::{BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    time::Duration,
 };
 
-mod days;
+use crate::cli::{choose_unit, get_option, get_params, time_function};
+use crate::shrink::{Predicate, Runner};
 
-fn time_function<T>(f: impl FnOnce() -> T) -> (T, Duration) {
-    let now = Instant::now();
-    (f(), now.elapsed())
-}
+use crate::days::Day;
 
-fn choose_unit(duration: Duration) -> (u128, String) {
-    if duration < Duration::from_millis(10) {
-        (duration.as_micros(), "µs".to_string())
-    } else if duration < Duration::from_secs(10) {
-        (duration.as_millis(), "ms".to_string())
-    } else {
-        (duration.as_secs() as u128, "s".to_string())
+mod cli;
+mod client;
+mod dashboard;
+mod days;
+mod encryption;
+mod fixtures;
+mod lint;
+#[cfg(test)]
+mod mock_site;
+mod normalize;
+mod numeric;
+mod queries;
+mod report;
+mod server;
+mod shrink;
+mod solver;
+mod trace;
+mod watch;
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(message) => *message,
+        Err(payload) => payload
+            .downcast_ref::<&str>()
+            .map_or("panicked".to_string(), |message| message.to_string()),
     }
 }
 
-fn run_day(day: impl Day, duration: Duration) {
+fn run_day(day: &dyn Day, duration: Duration) {
     let (time_parse, unit_parse) = choose_unit(duration);
     println!("Parse time : {}{}\n", time_parse, unit_parse);
 
-    let (result1, duration1) = time_function(|| day.solution1());
+    let (result1, duration1) = time_function(|| {
+        let _part = trace::span("part", &[("number", &1)]);
+        day.solution1()
+    });
     let (time1, unit1) = choose_unit(duration1);
     println!("Solution 1 : {} ({}{})", result1, time1, unit1);
 
-    let (result2, duration2) = time_function(|| day.solution2());
+    let (result2, duration2) = time_function(|| {
+        let _part = trace::span("part", &[("number", &2)]);
+        day.solution2()
+    });
     let (time2, unit2) = choose_unit(duration2);
     println!("Solution 2 : {} ({}{})", result2, time2, unit2);
 }
 
-macro_rules! run_day {
-    ($day_struct:ty, $input:ident) => {
-        let (day, duration) = time_function(move || <$day_struct>::make_day($input));
-        run_day(day, duration);
-    };
+fn enable_explain(args: &[String]) {
+    let format = args
+        .iter()
+        .find_map(|arg| arg.strip_prefix("--explain"))
+        .map(|format| match format {
+            "" | "=indented" => trace::Format::Indented,
+            "=json" => trace::Format::JsonLines,
+            _ => panic!("explain format should be indented or json"),
+        });
+    if let Some(format) = format {
+        trace::enable(format);
+    }
 }
 
-fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let day_number = args.get(1).expect("No day specified").as_str();
-    let use_test_input = args.get(2).map_or(false, |s| s == "test");
+fn check_strict(args: &[String], input: &str) {
+    if !args.iter().any(|arg| arg == "--strict") {
+        return;
+    }
+    let issues = normalize::normalize(input).issues;
+    if !issues.is_empty() {
+        for issue in issues.iter() {
+            eprintln!("{}", issue);
+        }
+        eprintln!("input needs normalizing, run without --strict to fix it on the fly");
+        std::process::exit(1);
+    }
+}
 
-    let path_input = if use_test_input {
-        format!("./inputs/day{}/input_test.txt", day_number)
+fn get_year(args: &[String]) -> u32 {
+    get_option(args, "--year").map_or(days::latest_year(), |year| {
+        year.parse().expect("year should be a number")
+    })
+}
+
+/// The directory holding the `dayN` directories of a year's inputs, `./inputs/<year>` unless
+/// `AOC_INPUTS_<year>` names another one.
+fn input_root(year: u32) -> PathBuf {
+    std::env::var_os(format!("AOC_INPUTS_{}", year)).map_or_else(
+        || Path::new("./inputs").join(year.to_string()),
+        PathBuf::from,
+    )
+}
+
+fn get_day_number(args: &[String]) -> u32 {
+    args.first()
+        .expect("No day specified")
+        .parse()
+        .expect("day should be a number")
+}
+
+fn read_input(year: u32, day_number: u32, args: &[String]) -> String {
+    let path = get_input_path(year, day_number, args);
+    encryption::read_input_file(Path::new(&path)).unwrap_or_else(|error| panic!("{}", error))
+}
+
+fn get_input_path(year: u32, day_number: u32, args: &[String]) -> PathBuf {
+    if let Some(path) = get_option(args, "--input") {
+        PathBuf::from(path)
+    } else if args.iter().any(|s| s == "test") {
+        input_root(year).join(format!("day{}/input_test.txt", day_number))
     } else {
-        format!("./inputs/day{}/input.txt", day_number)
-    };
+        input_root(year).join(format!("day{}/input.txt", day_number))
+    }
+}
 
-    let input = File::open(path_input).expect("File not found");
+fn solve(args: &[String]) {
+    let year = get_year(args);
+    let day_number = get_day_number(args);
+    enable_explain(args);
+    let input = read_input(year, day_number, args);
+    check_strict(args, &input);
 
-    match day_number {
-        "1" => {
-            run_day!(Day1, input);
-        }
-        "2" => {
-            run_day!(Day2, input);
-        }
-        "3" => {
-            run_day!(Day3, input);
-        }
-        "4" => {
-            run_day!(Day4, input);
-        }
-        "5" => {
-            run_day!(Day5, input);
-        }
-        "6" => {
-            run_day!(Day6, input);
-        }
-        "7" => {
-            run_day!(Day7, input);
-        }
-        "8" => {
-            run_day!(Day8, input);
-        }
-        "9" => {
-            run_day!(Day9, input);
-        }
-        "10" => {
-            run_day!(Day10, input);
-        }
-        "11" => {
-            run_day!(Day11, input);
-        }
-        "12" => {
-            run_day!(Day12, input);
-        }
-        "13" => {
-            run_day!(Day13, input);
-        }
-        "14" => {
-            run_day!(Day14, input);
-        }
-        "15" => {
-            run_day!(Day15, input);
-        }
-        "16" => {
-            run_day!(Day16, input);
-        }
-        "17" => {
-            run_day!(Day17, input);
-        }
-        "18" => {
-            run_day!(Day18, input);
-        }
-        "19" => {
-            run_day!(Day19, input);
+    let (day, duration) = time_function(|| days::make_day(year, day_number, input.as_bytes()));
+    let mut day = day.expect("day not found");
+    for (name, value) in get_params(args) {
+        day.set_param(name, value).expect("invalid parameter");
+    }
+    run_day(day.as_ref(), duration);
+}
+
+fn answer(args: &[String]) {
+    let day_number = get_day_number(args);
+    let part: u8 = args
+        .get(1)
+        .expect("No part specified")
+        .parse()
+        .expect("part should be a number");
+    enable_explain(args);
+    let mut input = String::new();
+    std::io::stdin()
+        .read_to_string(&mut input)
+        .expect("input should be utf-8 text");
+    check_strict(args, &input);
+    let mut day =
+        days::make_day(get_year(args), day_number, input.as_bytes()).expect("day not found");
+    for (name, value) in get_params(&args[2..]) {
+        day.set_param(name, value).expect("invalid parameter");
+    }
+    match part {
+        1 => println!("{}", day.solution1()),
+        2 => println!("{}", day.solution2()),
+        _ => panic!("part should be 1 or 2"),
+    }
+}
+
+fn calibrate(args: &[String]) {
+    let part =
+        get_option(args, "--part").map_or(2, |part| part.parse().expect("part should be a number"));
+    let reader: Box<dyn BufRead> = match get_option(args, "--input") {
+        Some(path) => Box::new(BufReader::new(File::open(path).expect("File not found"))),
+        None => Box::new(std::io::stdin().lock()),
+    };
+    if args.iter().any(|arg| arg == "--report") {
+        return diagnose_calibration(reader, args);
+    }
+    let mut calibration = days::y2023::day1::Calibration::new(reader, part)
+        .unwrap_or_else(|error| panic!("{}", error));
+    for (name, value) in get_params(args) {
+        calibration
+            .set_param(name, value)
+            .expect("invalid parameter");
+    }
+    let show_lines = args.iter().any(|arg| arg == "--lines");
+    for line in calibration.by_ref() {
+        let line = line.unwrap_or_else(|error| panic!("{}", error));
+        if show_lines {
+            println!(
+                "line {}: {} (total {})",
+                line.line_number, line.value, line.total
+            );
         }
-        "20" => {
-            run_day!(Day20, input);
+    }
+    println!("{}", calibration.total());
+}
+
+fn diagnose_calibration(reader: impl BufRead, args: &[String]) {
+    let mut diagnostics = days::y2023::day1::Diagnostics::new(reader);
+    for (name, value) in get_params(args) {
+        diagnostics
+            .set_param(name, value)
+            .expect("invalid parameter");
+    }
+    let (mut nb_lines, mut without_digits, mut differing) = (0, vec![], vec![]);
+    for diagnostic in diagnostics {
+        let diagnostic = diagnostic.unwrap_or_else(|error| panic!("{}", error));
+        println!("{}", diagnostic);
+        nb_lines += 1;
+        if !diagnostic.has_digits() {
+            without_digits.push(diagnostic.line_number.to_string());
+        } else if diagnostic.parts_differ() {
+            differing.push(diagnostic.line_number.to_string());
         }
-        "21" => {
-            run_day!(Day21, input);
+    }
+    let list = |line_numbers: &[String]| match line_numbers {
+        [] => String::new(),
+        _ => format!(" (lines {})", line_numbers.join(", ")),
+    };
+    println!(
+        "\n{} lines, {} without digits{}, {} where the parts differ{}",
+        nb_lines,
+        without_digits.len(),
+        list(&without_digits),
+        differing.len(),
+        list(&differing)
+    );
+}
+
+fn query(command: &str, args: &[String]) {
+    let year = get_year(args);
+    let (day_number, query) = queries::find_query(command, year)
+        .unwrap_or_else(|| panic!("no {} command in {}", command, year));
+    query(args, &|| {
+        normalize::normalize(&read_input(year, day_number, args)).text
+    });
+}
+
+fn lint(args: &[String]) {
+    let year = get_year(args);
+    let day_number = get_day_number(args);
+    let input = read_input(year, day_number, args);
+    let problems = days::lint(year, day_number, &input).expect("day not found");
+    for problem in problems.iter() {
+        println!("{}", problem);
+    }
+    if !problems.is_empty() {
+        std::process::exit(1);
+    }
+}
+
+fn generate(args: &[String]) {
+    let day_number = get_day_number(args);
+    let seed =
+        get_option(args, "--seed").map_or(0, |seed| seed.parse().expect("seed should be a number"));
+    let size = get_option(args, "--size")
+        .map_or(100, |size| size.parse().expect("size should be a number"));
+    let input =
+        days::generate_input(get_year(args), day_number, seed, size).expect("day not found");
+    print!("{}", input);
+}
+
+fn shrink(args: &[String]) {
+    let year = get_year(args);
+    let day_number = get_day_number(args);
+    let input = read_input(year, day_number, args);
+    let variant_params = args
+        .windows(2)
+        .filter(|pair| pair[0] == "--variant")
+        .map(|pair| {
+            let (name, value) = pair[1]
+                .split_once('=')
+                .expect("variant should be name=value");
+            (name.to_string(), value.to_string())
+        })
+        .collect::<Vec<_>>();
+    let predicate = if let Some(reference) = get_option(args, "--reference") {
+        Predicate::Reference(reference.to_string())
+    } else if !variant_params.is_empty() {
+        Predicate::Variant(variant_params)
+    } else {
+        Predicate::Panic
+    };
+    let runner = Runner {
+        program: std::env::current_exe().expect("should know its own path"),
+        year,
+        day_number,
+        part: get_option(args, "--part")
+            .map_or(1, |part| part.parse().expect("part should be a number")),
+        params: get_params(args)
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+        timeout: Duration::from_secs(get_option(args, "--timeout").map_or(10, |timeout| {
+            timeout.parse().expect("timeout should be a number")
+        })),
+    };
+    match shrink::shrink_failing_input(&runner, &predicate, &input) {
+        Ok(shrunk) => print!("{}", shrunk),
+        Err(error) => panic!("{}", error),
+    }
+}
+
+fn encrypt(args: &[String]) {
+    let key = encryption::load_key().unwrap_or_else(|error| panic!("{}", error));
+    for path in args.iter().map(Path::new) {
+        let plaintext = std::fs::read(path).expect("File not found");
+        let encrypted_path = encryption::encrypted_path(path);
+        std::fs::write(&encrypted_path, encryption::encrypt(&key, &plaintext))
+            .expect("could write encrypted input");
+        println!("{}", encrypted_path.display());
+    }
+}
+
+fn decrypt(args: &[String]) {
+    let key = encryption::load_key().unwrap_or_else(|error| panic!("{}", error));
+    let path = args.first().expect("No file specified");
+    let encrypted = std::fs::read(path).expect("File not found");
+    let plaintext =
+        encryption::decrypt(&key, &encrypted).unwrap_or_else(|error| panic!("{}", error));
+    std::io::stdout()
+        .write_all(&plaintext)
+        .expect("could write input");
+}
+
+fn serve(args: &[String]) {
+    let parse = |name, default| {
+        get_option(args, name).map_or(default, |value| {
+            value
+                .parse()
+                .unwrap_or_else(|_| panic!("{} should be a number", name))
+        })
+    };
+    let defaults = server::Config::default();
+    let config = server::Config {
+        port: parse("--port", defaults.port as usize) as u16,
+        nb_threads: parse("--threads", defaults.nb_threads),
+        max_input_size: parse("--max-input-size", defaults.max_input_size),
+    };
+    let service = server::Service::bind(config).unwrap_or_else(|error| panic!("{}", error));
+    println!("Listening on http://127.0.0.1:{}", service.port());
+    service.run();
+}
+
+fn dashboard() {
+    let manifest = fixtures::Manifest::load().unwrap_or_else(|error| panic!("{}", error));
+    dashboard::run(manifest).expect("could draw the dashboard");
+}
+
+fn watch(args: &[String]) {
+    let year = get_year(args);
+    let day_number = get_day_number(args);
+    let input_path = get_input_path(year, day_number, args);
+    let input_root = match get_option(args, "--input") {
+        Some(_) => None,
+        None => Some(input_root(year)),
+    };
+    watch::watch(
+        year,
+        day_number,
+        &input_path,
+        input_root.as_deref(),
+        &get_params(args),
+    )
+    .unwrap_or_else(|error| panic!("{}", error));
+}
+
+fn report(args: &[String]) {
+    let mut years = args
+        .windows(2)
+        .filter(|pair| pair[0] == "--year")
+        .map(|pair| pair[1].parse().expect("year should be a number"))
+        .collect::<Vec<u32>>();
+    if years.is_empty() {
+        years = days::years();
+    }
+    let manifest = fixtures::Manifest::load().unwrap_or_else(|error| panic!("{}", error));
+    let entries = report::collect(&years, input_root, &manifest);
+    print!("{}", report::format_report(&entries));
+    if let Some(path) = get_option(args, "--html") {
+        std::fs::write(path, report::format_html(&entries)).expect("could write html report");
+    }
+}
+
+/// Where the time of the last request to the puzzle site is kept.
+const SITE_STAMP_PATH: &str = "./inputs/.last_request";
+
+fn site_client() -> client::Client {
+    let config = client::Config::from_env(PathBuf::from(SITE_STAMP_PATH))
+        .unwrap_or_else(|error| panic!("{}", error));
+    client::Client::new(config)
+}
+
+fn fetch(args: &[String]) {
+    let year = get_year(args);
+    let day_number = get_day_number(args);
+    let path = get_input_path(year, day_number, args);
+    match site_client().download_input(year, day_number, &path) {
+        Ok(true) => println!("{}", path.display()),
+        Ok(false) => println!("{} (already there)", path.display()),
+        Err(error) => panic!("{}", error),
+    }
+}
+
+fn examples(args: &[String]) {
+    let year = get_year(args);
+    let day_number = get_day_number(args);
+    let examples = site_client()
+        .examples(year, day_number)
+        .unwrap_or_else(|error| panic!("{}", error));
+    for (index, example) in examples.iter().enumerate() {
+        println!("--- example {} ---\n{}", index + 1, example);
+    }
+}
+
+fn submit(args: &[String]) {
+    let year = get_year(args);
+    let day_number = get_day_number(args);
+    let part: u8 = args
+        .get(1)
+        .expect("No part specified")
+        .parse()
+        .expect("part should be a number");
+    let answer = match args.get(2).filter(|arg| !arg.starts_with("--")) {
+        Some(answer) => answer.clone(),
+        None => {
+            let input = read_input(year, day_number, args);
+            let day = days::make_day(year, day_number, input.as_bytes()).expect("day not found");
+            match part {
+                1 => day.solution1(),
+                2 => day.solution2(),
+                _ => panic!("part should be 1 or 2"),
+            }
         }
-        _ => panic!("day not found"),
     };
+    let input_path = get_input_path(year, day_number, args);
+    let history_path = client::History::path(input_path.parent().unwrap_or(Path::new(".")));
+    let mut history =
+        client::History::load(&history_path).unwrap_or_else(|error| panic!("{}", error));
+    let verdict = site_client()
+        .submit(year, day_number, part, &answer, &mut history)
+        .unwrap_or_else(|error| panic!("{}", error));
+    history
+        .save(&history_path)
+        .unwrap_or_else(|error| panic!("{}", error));
+    println!("{}: {}", answer, verdict);
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    match args.get(1).map(String::as_str) {
+        Some("answer") => answer(&args[2..]),
+        Some("calibrate") => calibrate(&args[2..]),
+        Some("dashboard") => dashboard(),
+        Some("decrypt") => decrypt(&args[2..]),
+        Some("encrypt") => encrypt(&args[2..]),
+        Some("examples") => examples(&args[2..]),
+        Some("fetch") => fetch(&args[2..]),
+        Some("gen") => generate(&args[2..]),
+        Some("lint") => lint(&args[2..]),
+        Some("report") => report(&args[2..]),
+        Some("serve") => serve(&args[2..]),
+        Some("shrink") => shrink(&args[2..]),
+        Some("submit") => submit(&args[2..]),
+        Some("watch") => watch(&args[2..]),
+        Some(command) if queries::is_query(command) => query(command, &args[2..]),
+        _ => solve(&args[1..]),
+    }
 }
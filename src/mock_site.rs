@@ -0,0 +1,129 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tiny_http::{Method, Response, Server};
+
+/// A puzzle served by the mock site.
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub input: String,
+    pub examples: Vec<String>,
+    pub answers: [String; 2],
+}
+
+/// Serves puzzles on the loopback interface the way the puzzle site does, answering submissions
+/// with the same sentences, so that the client can be tested without network.
+pub struct MockSite {
+    pub base_url: String,
+    /// Number of requests received so far.
+    pub nb_requests: Arc<AtomicUsize>,
+}
+
+pub const SESSION: &str = "mock-session";
+
+impl MockSite {
+    pub fn start(puzzles: Vec<Puzzle>) -> MockSite {
+        let server = Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        let nb_requests = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&nb_requests);
+        std::thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let logged_in = request.headers().iter().any(|header| {
+                    header.field.equiv("Cookie")
+                        && header.value.as_str() == format!("session={}", SESSION)
+                });
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let (status, text) = if logged_in {
+                    respond(&puzzles, request.method(), request.url(), &body)
+                } else {
+                    (
+                        400,
+                        "Puzzle inputs differ by user.  Please log in.".to_string(),
+                    )
+                };
+                let _ = request.respond(Response::from_string(text).with_status_code(status));
+            }
+        });
+        MockSite {
+            base_url: format!("http://127.0.0.1:{}", port),
+            nb_requests,
+        }
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn respond(puzzles: &[Puzzle], method: &Method, url: &str, body: &str) -> (u16, String) {
+    let segments = url.split('/').skip(1).collect::<Vec<_>>();
+    let (year, day, rest) = match segments.as_slice() {
+        [year, "day", day, rest @ ..] => (year.parse().ok(), day.parse().ok(), rest),
+        _ => return (404, "404 Not Found".to_string()),
+    };
+    let Some(puzzle) = puzzles
+        .iter()
+        .find(|puzzle| Some(puzzle.year) == year && Some(puzzle.day) == day)
+    else {
+        return (404, "404 Not Found".to_string());
+    };
+    match (method, rest) {
+        (Method::Get, []) => {
+            let mut page = "<html><body><main><article class=\"day-desc\">".to_string();
+            for example in puzzle.examples.iter() {
+                page += "<p>For example:</p>\n<pre><code>";
+                page += &escape(example);
+                page += "</code></pre>\n<p>In this example, the answer is <code><em>1</em></code>.</p>\n";
+            }
+            (200, page + "</article></main></body></html>")
+        }
+        (Method::Get, ["input"]) => (200, puzzle.input.clone()),
+        (Method::Post, ["answer"]) => {
+            let form = body
+                .split('&')
+                .filter_map(|field| field.split_once('='))
+                .collect::<Vec<_>>();
+            let field = |name| form.iter().find(|(field, _)| *field == name).map(|f| f.1);
+            let (Some(level), Some(answer)) = (field("level"), field("answer")) else {
+                return (400, "Bad request".to_string());
+            };
+            let expected = match level {
+                "1" => &puzzle.answers[0],
+                _ => &puzzle.answers[1],
+            };
+            let sentence = if answer == "wait" {
+                "You gave an answer too recently; you have to wait after submitting an answer \
+                 before trying again.  You have 1m 5s left to wait."
+                    .to_string()
+            } else if answer == expected {
+                "That's the right answer!  You are one gold star closer to restoring snow \
+                 operations."
+                    .to_string()
+            } else {
+                let hint = match (answer.parse::<i64>(), expected.parse::<i64>()) {
+                    (Ok(answer), Ok(expected)) if answer > expected => "your answer is too high",
+                    (Ok(answer), Ok(expected)) if answer < expected => "your answer is too low",
+                    _ => "If you're stuck, make sure you're using the full input data",
+                };
+                format!(
+                    "That's not the right answer; {}.  Please wait one minute before trying \
+                     again.",
+                    hint
+                )
+            };
+            (
+                200,
+                format!(
+                    "<html><body><main><article><p>{}</p></article></main></body></html>",
+                    sentence
+                ),
+            )
+        }
+        _ => (404, "404 Not Found".to_string()),
+    }
+}
@@ -0,0 +1,154 @@
+use std::fmt;
+
+const BOM: char = '\u{feff}';
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IssueKind {
+    ByteOrderMark,
+    CarriageReturn,
+    TrailingWhitespace,
+    TrailingBlankLine,
+    MissingFinalNewline,
+}
+
+/// Something in an input file that the days do not expect, found on a line counted from 1.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Issue {
+    pub line: usize,
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self.kind {
+            IssueKind::ByteOrderMark => "byte order mark",
+            IssueKind::CarriageReturn => "carriage return",
+            IssueKind::TrailingWhitespace => "trailing whitespace",
+            IssueKind::TrailingBlankLine => "trailing blank line",
+            IssueKind::MissingFinalNewline => "missing final newline",
+        };
+        write!(f, "line {}: {}", self.line, description)
+    }
+}
+
+pub struct Normalized {
+    pub text: String,
+    pub issues: Vec<Issue>,
+}
+
+/// Turns an input as saved by any editor into `\n`-terminated lines without trailing whitespace
+/// nor trailing blank lines, listing what had to be fixed.
+pub fn normalize(input: &str) -> Normalized {
+    let mut issues = vec![];
+    let input = match input.strip_prefix(BOM) {
+        Some(input) => {
+            issues.push(Issue {
+                line: 1,
+                kind: IssueKind::ByteOrderMark,
+            });
+            input
+        }
+        None => input,
+    };
+
+    let mut lines = input.split('\n').collect::<Vec<_>>();
+    if lines.last() == Some(&"") {
+        lines.pop();
+    } else if !input.is_empty() {
+        issues.push(Issue {
+            line: lines.len(),
+            kind: IssueKind::MissingFinalNewline,
+        });
+    }
+
+    let mut text_lines = Vec::with_capacity(lines.len());
+    for (index, line) in lines.iter().enumerate() {
+        let line_number = index + 1;
+        let line = match line.strip_suffix('\r') {
+            Some(line) => {
+                issues.push(Issue {
+                    line: line_number,
+                    kind: IssueKind::CarriageReturn,
+                });
+                line
+            }
+            None => line,
+        };
+        let trimmed = line.trim_end();
+        if trimmed.len() != line.len() {
+            issues.push(Issue {
+                line: line_number,
+                kind: IssueKind::TrailingWhitespace,
+            });
+        }
+        text_lines.push(trimmed);
+    }
+
+    while text_lines.last() == Some(&"") {
+        text_lines.pop();
+        issues.push(Issue {
+            line: text_lines.len() + 1,
+            kind: IssueKind::TrailingBlankLine,
+        });
+    }
+    issues.sort_by_key(|issue| issue.line);
+
+    let text = text_lines
+        .iter()
+        .map(|line| format!("{}\n", line))
+        .collect();
+    Normalized { text, issues }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        let normalized = normalize("\u{feff}32T3K 765\r\nT55J5 684  \r\n\r\n \nKK677 28");
+        assert_eq!(normalized.text, "32T3K 765\nT55J5 684\n\n\nKK677 28\n");
+        let issues = normalized
+            .issues
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            issues,
+            [
+                "line 1: byte order mark",
+                "line 1: carriage return",
+                "line 2: carriage return",
+                "line 2: trailing whitespace",
+                "line 3: carriage return",
+                "line 4: trailing whitespace",
+                "line 5: missing final newline",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_normalize_trailing_blank_lines() {
+        let normalized = normalize("#.#\n..#\n\n  \n");
+        assert_eq!(normalized.text, "#.#\n..#\n");
+        assert_eq!(
+            normalized.issues,
+            [
+                Issue {
+                    line: 3,
+                    kind: IssueKind::TrailingBlankLine
+                },
+                Issue {
+                    line: 4,
+                    kind: IssueKind::TrailingWhitespace
+                },
+                Issue {
+                    line: 4,
+                    kind: IssueKind::TrailingBlankLine
+                },
+            ]
+        );
+        assert!(normalize("#.#\n").issues.is_empty());
+        assert_eq!(normalize("").text, "");
+    }
+}
@@ -0,0 +1,178 @@
+use num::{BigInt, CheckedAdd, CheckedMul, CheckedSub};
+use std::fmt;
+use std::str::FromStr;
+
+/// A value no longer fitting in the 64-bit integers days compute with by default.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "overflow of a 64-bit integer")
+    }
+}
+
+/// The integers the answers growing with the input are computed with: `i64`, every operation of
+/// which is checked, or `BigInt`, which never overflows.
+pub trait Number:
+    num::Integer
+    + num::integer::Roots
+    + CheckedAdd
+    + CheckedSub
+    + CheckedMul
+    + Clone
+    + fmt::Display
+    + FromStr
+    + From<i64>
+{
+}
+
+impl Number for i64 {}
+impl Number for BigInt {}
+
+/// How the days supporting the `bigint` parameter compute their answers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    #[default]
+    Checked,
+    Big,
+}
+
+impl Precision {
+    pub fn from_param(value: &str) -> Result<Precision, String> {
+        match value.parse() {
+            Ok(true) => Ok(Precision::Big),
+            Ok(false) => Ok(Precision::Checked),
+            Err(_) => Err("should be a boolean".to_string()),
+        }
+    }
+}
+
+pub fn add<N: Number>(a: &N, b: &N) -> Result<N, Overflow> {
+    a.checked_add(b).ok_or(Overflow)
+}
+
+pub fn sub<N: Number>(a: &N, b: &N) -> Result<N, Overflow> {
+    a.checked_sub(b).ok_or(Overflow)
+}
+
+pub fn mul<N: Number>(a: &N, b: &N) -> Result<N, Overflow> {
+    a.checked_mul(b).ok_or(Overflow)
+}
+
+pub fn pow<N: Number>(base: N, exponent: usize) -> Result<N, Overflow> {
+    num::checked_pow(base, exponent).ok_or(Overflow)
+}
+
+pub fn lcm<N: Number>(a: &N, b: &N) -> Result<N, Overflow> {
+    mul(&(a.clone() / a.gcd(b)), b)
+}
+
+pub fn sum<N: Number>(
+    values: impl IntoIterator<Item = Result<N, Overflow>>,
+) -> Result<N, Overflow> {
+    values
+        .into_iter()
+        .try_fold(N::from(0), |total, value| add(&total, &value?))
+}
+
+pub fn product<N: Number>(
+    values: impl IntoIterator<Item = Result<N, Overflow>>,
+) -> Result<N, Overflow> {
+    values
+        .into_iter()
+        .try_fold(N::from(1), |total, value| mul(&total, &value?))
+}
+
+/// Parses digits already checked by the parser of the day, failing only when they are too many.
+pub fn parse<N: Number>(digits: &str) -> Result<N, Overflow> {
+    digits.parse().map_err(|_| Overflow)
+}
+
+/// The answer of a part, panicking on overflow as days do on any other failure.
+pub fn answer(result: Result<impl fmt::Display, Overflow>) -> String {
+    match result {
+        Ok(value) => value.to_string(),
+        Err(overflow) => panic!("{}", overflow),
+    }
+}
+
+/// The answer of a part computed with the precision asked for, the same computation being given
+/// for both integer types.
+pub fn solve(
+    precision: Precision,
+    checked: impl FnOnce() -> Result<i64, Overflow>,
+    big: impl FnOnce() -> Result<BigInt, Overflow>,
+) -> String {
+    try_solve(precision, checked, big).unwrap_or_else(|error| panic!("{}", error))
+}
+
+/// Like `solve`, the overflow being returned as the reason there is no answer.
+pub fn try_solve(
+    precision: Precision,
+    checked: impl FnOnce() -> Result<i64, Overflow>,
+    big: impl FnOnce() -> Result<BigInt, Overflow>,
+) -> Result<String, String> {
+    match precision {
+        Precision::Checked => checked()
+            .map(|value| value.to_string())
+            .map_err(|overflow| format!("{}, run with --bigint for an exact answer", overflow)),
+        Precision::Big => big()
+            .map(|value| value.to_string())
+            .map_err(|overflow| overflow.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked_and_big() {
+        let big = 1i64 << 62;
+        assert_eq!(add(&big, &-big), Ok(0));
+        assert_eq!(add(&big, &big), Err(Overflow));
+        assert_eq!(mul(&big, &2), Err(Overflow));
+        assert_eq!(sub(&-big, &big), Ok(i64::MIN));
+        assert_eq!(pow(2i64, 63), Err(Overflow));
+        assert_eq!(lcm(&(big / 2), &(big / 4 * 3)), Ok(big / 4 * 6));
+        assert_eq!(lcm(&big, &3), Err(Overflow));
+        assert_eq!(
+            sum([big, big, big].map(|value| Ok(BigInt::from(value)))),
+            Ok(BigInt::from(3) << 62)
+        );
+        assert_eq!(product((1..=25).map(|n| Ok(n as i64))), Err(Overflow));
+        assert_eq!(
+            product((1..=25).map(|n| Ok(BigInt::from(n))))
+                .unwrap()
+                .to_string(),
+            "15511210043330985984000000"
+        );
+        assert_eq!(parse::<i64>("99999999999999999999"), Err(Overflow));
+        assert_eq!(
+            parse::<BigInt>("99999999999999999999").map(|n| n.to_string()),
+            Ok("99999999999999999999".to_string())
+        );
+    }
+
+    #[test]
+    fn test_precision_and_answer() {
+        assert_eq!(Precision::from_param("true"), Ok(Precision::Big));
+        assert_eq!(Precision::from_param("false"), Ok(Precision::Checked));
+        assert!(Precision::from_param("yes").is_err());
+        assert_eq!(answer(Ok::<_, Overflow>(42)), "42");
+        let panicked = std::panic::catch_unwind(|| answer(Err::<i64, _>(Overflow)));
+        assert_eq!(
+            crate::panic_message(panicked.unwrap_err()),
+            "overflow of a 64-bit integer"
+        );
+        let big = || Ok(BigInt::from(1) << 64);
+        assert_eq!(
+            solve(Precision::Big, || Err(Overflow), big),
+            "18446744073709551616"
+        );
+        let panicked =
+            std::panic::catch_unwind(|| solve(Precision::Checked, || Err(Overflow), big));
+        assert!(crate::panic_message(panicked.unwrap_err()).contains("--bigint"));
+    }
+}
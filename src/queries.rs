@@ -0,0 +1,236 @@
+use crate::cli::{choose_unit, get_option, get_params, time_function};
+use crate::days::y2023::{day2, day3, day4};
+use crate::days::{self, Day};
+use crate::numeric;
+
+/// A command exploring the model of a day beyond its answers, given its arguments and a reader of
+/// the normalized input of the day. It parses the arguments and prints what the day computes.
+pub type Query = fn(args: &[String], read_input: &dyn Fn() -> String);
+
+/// The queries by command name, with the year and the day they belong to.
+const QUERIES: [(&str, u32, u32, Query); 3] = [
+    ("cubes", 2023, 2, cubes),
+    ("schematic", 2023, 3, schematic),
+    ("scratchcards", 2023, 4, scratchcards),
+];
+
+pub fn is_query(command: &str) -> bool {
+    QUERIES.iter().any(|(name, ..)| *name == command)
+}
+
+/// The day a query of a year reads the input of, and the query.
+pub fn find_query(command: &str, year: u32) -> Option<(u32, Query)> {
+    QUERIES
+        .iter()
+        .find(|(name, query_year, ..)| *name == command && *query_year == year)
+        .map(|(_, _, day_number, query)| (*day_number, *query))
+}
+
+/// Answers questions about the cube games of day 2 of 2023.
+fn cubes(args: &[String], read_input: &dyn Fn() -> String) {
+    let query = args
+        .first()
+        .expect("No query specified, minimal-bag, bags or probabilities");
+    let day = day2::Day2::make_day(read_input().as_bytes());
+    match query.as_str() {
+        "minimal-bag" => println!("{}", day2::format_bag(&day.minimal_bag())),
+        "bags" => {
+            let budget = get_option(args, "--budget")
+                .expect("No budget specified")
+                .parse()
+                .expect("budget should be a number");
+            let game_ids = get_option(args, "--games").map_or(vec![], |ids| {
+                ids.split(',')
+                    .map(|id| id.parse().expect("game ids should be numbers"))
+                    .collect()
+            });
+            let bags = day
+                .bags_admitting(&game_ids, budget)
+                .unwrap_or_else(|error| panic!("{}", error));
+            for bag in bags {
+                println!("{}", day2::format_bag(&bag));
+            }
+        }
+        "probabilities" => {
+            let bag = day2::parse_bag(get_option(args, "--bag").expect("No bag specified"))
+                .unwrap_or_else(|error| panic!("{}", error));
+            for (id, probabilities) in day.grab_probabilities(&bag) {
+                let probabilities = probabilities
+                    .iter()
+                    .map(|probability| format!("{:.6}", probability))
+                    .collect::<Vec<_>>();
+                println!("Game {}: {}", id, probabilities.join("; "));
+            }
+        }
+        _ => panic!("query should be minimal-bag, bags or probabilities"),
+    }
+}
+
+/// Answers questions about the engine schematic of day 3 of 2023.
+fn schematic(args: &[String], read_input: &dyn Fn() -> String) {
+    let query = args
+        .first()
+        .expect("No query specified, parts, symbols, components or rule");
+    let schematic = day3::Schematic::from_input(read_input().as_bytes());
+    let describe_symbol = |symbol| {
+        let neighbours = schematic
+            .neighbours(symbol)
+            .map(|number| number.value.to_string())
+            .collect::<Vec<_>>();
+        format!("{}: {}", schematic.symbol(symbol), neighbours.join(", "))
+    };
+    match query.as_str() {
+        "parts" => {
+            for number in schematic.numbers_next_to_symbols() {
+                println!("{}", number);
+            }
+        }
+        "symbols" => {
+            let nb_neighbours = get_option(args, "--neighbours")
+                .expect("No number of neighbours specified")
+                .parse()
+                .expect("number of neighbours should be a number");
+            for symbol in schematic.symbols_with_neighbours(nb_neighbours) {
+                println!("{}", describe_symbol(symbol));
+            }
+        }
+        "components" => {
+            for component in schematic.components() {
+                let numbers = component
+                    .numbers
+                    .iter()
+                    .map(|number| schematic.number(*number).value.to_string())
+                    .collect::<Vec<_>>();
+                let symbols = component
+                    .symbols
+                    .iter()
+                    .map(|symbol| schematic.symbol(*symbol).to_string())
+                    .collect::<Vec<_>>();
+                println!("{}: {}", symbols.join(", "), numbers.join(", "));
+            }
+        }
+        "rule" => {
+            let mut rule = day3::SymbolRule {
+                symbols: get_option(args, "--symbols").map(|symbols| symbols.chars().collect()),
+                neighbours: day3::NeighbourCount::AtLeast(1),
+                aggregation: day3::Aggregation::Sum,
+            };
+            if let Some(neighbours) = get_option(args, "--neighbours") {
+                rule.neighbours = neighbours
+                    .parse()
+                    .unwrap_or_else(|error| panic!("{}", error));
+            }
+            if let Some(aggregation) = get_option(args, "--aggregate") {
+                rule.aggregation = aggregation
+                    .parse()
+                    .unwrap_or_else(|error| panic!("{}", error));
+            }
+            let summary = schematic
+                .apply(&rule)
+                .unwrap_or_else(|error| panic!("{}", error));
+            for (character, value) in summary.by_symbol.iter() {
+                println!("{} {}", character, value);
+            }
+            println!("{} symbols, total {}", summary.nb_symbols, summary.total);
+        }
+        _ => panic!("query should be parts, symbols, components or rule"),
+    }
+}
+
+/// Scratches the cards of day 4 of 2023, or compares the ways of matching their numbers.
+fn scratchcards(args: &[String], read_input: &dyn Fn() -> String) {
+    if args.first().is_some_and(|arg| arg == "bench") {
+        return bench_matches(args);
+    }
+    let mut day = day4::Day4::make_day(read_input().as_bytes());
+    for (name, value) in get_params(args) {
+        day.set_param(name, value).expect("invalid parameter");
+    }
+    if args.iter().any(|arg| arg == "--bigint") {
+        print_cascade(day.cascade::<num::BigInt>());
+    } else {
+        print_cascade(day.cascade::<i64>());
+    }
+}
+
+fn bench_matches(args: &[String]) {
+    let nb_cards = get_option(args, "--cards").map_or(1_000_000, |nb_cards| {
+        nb_cards
+            .parse()
+            .expect("number of cards should be a number")
+    });
+    let seed =
+        get_option(args, "--seed").map_or(0, |seed| seed.parse().expect("seed should be a number"));
+    let format_time = |duration| {
+        let (time, unit) = choose_unit(duration);
+        format!("{}{}", time, unit)
+    };
+    let (input, duration) =
+        time_function(|| days::generate_input(2023, 4, seed, nb_cards).unwrap());
+    println!(
+        "Generated {} cards in {}\n",
+        nb_cards,
+        format_time(duration)
+    );
+    let (cards, duration) = time_function(|| day4::read_card_numbers(&input));
+    println!("Read their numbers in {}\n", format_time(duration));
+    // both ways count on the numbers already read, the index being built first
+    let (nb_matches, duration) = time_function(|| day4::count_matches_by_search(&cards));
+    println!(
+        "search: {} matches in {}",
+        nb_matches,
+        format_time(duration)
+    );
+    let (indexes, build_duration) = time_function(|| day4::index_cards(&cards));
+    let (nb_matches, count_duration) = time_function(|| day4::count_matches_by_index(&indexes));
+    println!(
+        "index:  {} matches in {}, built in {} and counted in {}",
+        nb_matches,
+        format_time(build_duration + count_duration),
+        format_time(build_duration),
+        format_time(count_duration)
+    );
+}
+
+fn print_cascade<N: numeric::Number>(cascade: Result<day4::Cascade<N>, day4::CascadeError>) {
+    let cascade = cascade.unwrap_or_else(|error| match error {
+        day4::CascadeError::Overflow(overflow) => {
+            panic!("{}, run with --bigint for exact counts", overflow)
+        }
+        error => panic!("{}", error),
+    });
+    println!("Card  Copies won  Copies given");
+    for card in cascade.cards.iter() {
+        println!(
+            "{:>4}  {:>10}  {:>12}",
+            card.id, card.copies_won, card.copies_given
+        );
+    }
+    println!();
+    println!("{} cards", numeric::answer(cascade.total()));
+    let chain = cascade
+        .longest_chain
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>();
+    println!("longest chain: {}", chain.join(" -> "));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::day_numbers;
+
+    #[test]
+    fn queries_read_registered_days() {
+        for (command, year, day_number, _) in QUERIES {
+            assert!(day_numbers(year).contains(&day_number), "{}", command);
+            assert_eq!(
+                find_query(command, year).map(|(day, _)| day),
+                Some(day_number)
+            );
+        }
+        assert!(is_query("cubes"));
+        assert!(find_query("cubes", 2022).is_none());
+    }
+}
@@ -0,0 +1,374 @@
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::cli::choose_unit;
+use crate::fixtures::Manifest;
+use crate::solver::{self, Answer};
+use crate::{days, encryption};
+
+/// What solving the puzzle input of a day gave: its parse time and answers, or why there is none.
+pub struct Entry {
+    pub year: u32,
+    pub day_number: u32,
+    pub solved: Result<(Duration, [Answer; 2]), String>,
+    /// The answers given for the input by the fixtures manifest.
+    pub expected: [Option<String>; 2],
+    /// The input, for the days whose input is a grid.
+    pub grid: Option<String>,
+}
+
+impl Entry {
+    fn time(&self) -> Duration {
+        match &self.solved {
+            Ok((parse_time, answers)) => {
+                *parse_time
+                    + answers
+                        .iter()
+                        .flatten()
+                        .map(|(_, duration)| *duration)
+                        .sum::<Duration>()
+            }
+            Err(_) => Duration::ZERO,
+        }
+    }
+
+    /// Whether the answer of a part is the expected one, when one is known.
+    fn check(&self, part: usize) -> Option<bool> {
+        let expected = self.expected[part].as_ref()?;
+        match &self.solved {
+            Ok((_, answers)) => Some(
+                answers[part]
+                    .as_ref()
+                    .is_ok_and(|(answer, _)| answer == expected),
+            ),
+            Err(_) => Some(false),
+        }
+    }
+
+    fn is_solved(&self) -> bool {
+        matches!(&self.solved, Ok((_, answers)) if answers.iter().all(Result::is_ok))
+    }
+}
+
+/// Solves the `input.txt` of every day of the years, found under `input_root(year)`, taking the
+/// expected answers from the manifest.
+pub fn collect(
+    years: &[u32],
+    input_root: impl Fn(u32) -> PathBuf,
+    manifest: &Manifest,
+) -> Vec<Entry> {
+    let mut entries = vec![];
+    for &year in years {
+        for day_number in days::day_numbers(year) {
+            let path = input_root(year).join(format!("day{}/input.txt", day_number));
+            let solved = if path.exists() || encryption::needs_key(&path) {
+                solver::solve(year, day_number, &path, &[])
+            } else {
+                Err("no input".to_string())
+            };
+            let example = manifest.find_input(&path);
+            let expected = [1, 2]
+                .map(|part| example.and_then(|example| example.expected(part).map(str::to_string)));
+            let grid = match solved {
+                Ok(_) if days::has_grid(year, day_number) => {
+                    encryption::read_input_file(&path).ok()
+                }
+                _ => None,
+            };
+            entries.push(Entry {
+                year,
+                day_number,
+                solved,
+                expected,
+                grid,
+            });
+        }
+    }
+    entries
+}
+
+fn format_time(duration: Duration) -> String {
+    let (time, unit) = choose_unit(duration);
+    format!("{}{}", time, unit)
+}
+
+fn format_total(label: &str, entries: &[&Entry]) -> String {
+    let nb_solved = entries.iter().filter(|entry| entry.is_solved()).count();
+    let time = entries.iter().map(|entry| entry.time()).sum();
+    format!(
+        "{:<9} {} of {} days solved in {}",
+        label,
+        nb_solved,
+        entries.len(),
+        format_time(time)
+    )
+}
+
+/// A table of the answers and timings of each day, with totals for each year and for them all,
+/// followed by the errors.
+pub fn format_report(entries: &[Entry]) -> String {
+    let cell = |answer: &Answer| match answer {
+        Ok((result, _)) => result.clone(),
+        Err(_) => "failed".to_string(),
+    };
+    let width = entries
+        .iter()
+        .filter_map(|entry| entry.solved.as_ref().ok())
+        .flat_map(|(_, answers)| answers.iter().map(|answer| cell(answer).len()))
+        .max()
+        .unwrap_or(0)
+        .max(6);
+
+    let mut report = String::new();
+    writeln!(
+        report,
+        "Year  Day  {:<width$}  {:<width$}  Time",
+        "Part 1", "Part 2"
+    )
+    .unwrap();
+    let mut errors = vec![];
+    for entry in entries {
+        let prefix = format!("{}  {:>3}  ", entry.year, entry.day_number);
+        match &entry.solved {
+            Ok((_, answers)) => {
+                writeln!(
+                    report,
+                    "{}{:<width$}  {:<width$}  {}",
+                    prefix,
+                    cell(&answers[0]),
+                    cell(&answers[1]),
+                    format_time(entry.time())
+                )
+                .unwrap();
+                for (part, answer) in answers.iter().enumerate() {
+                    if let Err(error) = answer {
+                        errors.push(format!(
+                            "{} day {} part {}: {}",
+                            entry.year,
+                            entry.day_number,
+                            part + 1,
+                            error
+                        ));
+                    }
+                }
+            }
+            Err(error) if error == "no input" => writeln!(report, "{}no input", prefix).unwrap(),
+            Err(error) => {
+                writeln!(report, "{}failed", prefix).unwrap();
+                errors.push(format!(
+                    "{} day {}: {}",
+                    entry.year, entry.day_number, error
+                ));
+            }
+        }
+    }
+
+    report.push('\n');
+    let mut years = entries.iter().map(|entry| entry.year).collect::<Vec<_>>();
+    years.dedup();
+    for &year in years.iter() {
+        let year_entries = entries
+            .iter()
+            .filter(|entry| entry.year == year)
+            .collect::<Vec<_>>();
+        writeln!(report, "{}", format_total(&year.to_string(), &year_entries)).unwrap();
+    }
+    if years.len() > 1 {
+        writeln!(
+            report,
+            "{}",
+            format_total("All years", &entries.iter().collect::<Vec<_>>())
+        )
+        .unwrap();
+    }
+    for error in errors {
+        writeln!(report, "\n{}", error).unwrap();
+    }
+    report
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2em}\
+table{border-collapse:collapse}td,th{padding:.2em .8em;text-align:left}\
+tr:nth-child(even){background:#f4f4f4}.pass{color:#1a7f37}.fail{color:#cf222e}\
+.unchecked{color:#6e7781}.bar{background:#54aeff;height:.8em;min-width:1px}\
+.timing{width:20em}pre{font-size:.6em;line-height:1}";
+
+/// A page made of the table of every day and part, its checks against the expected answers and
+/// bars of its timings, then the grids of the inputs, with the styles inline so that it can be
+/// shared as a single file.
+pub fn format_html(entries: &[Entry]) -> String {
+    let max_time = entries
+        .iter()
+        .map(Entry::time)
+        .max()
+        .unwrap_or_default()
+        .max(Duration::from_nanos(1));
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>Advent of code report</title>\
+         <style>{}</style></head><body>\n<h1>Advent of code report</h1>\n<table>\n\
+         <tr><th>Year</th><th>Day</th><th>Part 1</th><th>Part 2</th><th>Time</th><th></th></tr>\n",
+        STYLE
+    );
+    for entry in entries {
+        let mut cells = vec![];
+        for part in 0..2 {
+            let (class, text) = match (&entry.solved, entry.check(part)) {
+                (Err(error), _) => ("unchecked", error.clone()),
+                (Ok((_, answers)), check) => {
+                    let class = match check {
+                        Some(true) => "pass",
+                        Some(false) => "fail",
+                        None => "unchecked",
+                    };
+                    let text = match &answers[part] {
+                        Ok((answer, _)) => answer.clone(),
+                        Err(error) => error.clone(),
+                    };
+                    (class, text)
+                }
+            };
+            let title = match &entry.expected[part] {
+                Some(expected) => format!(" title=\"expected {}\"", escape_html(expected)),
+                None => String::new(),
+            };
+            cells.push(format!(
+                "<td class=\"{}\"{}>{}</td>",
+                class,
+                title,
+                escape_html(&text)
+            ));
+        }
+        let time = entry.time();
+        writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td>{}<td>{}</td>\
+             <td class=\"timing\"><div class=\"bar\" style=\"width:{:.1}%\"></div></td></tr>",
+            entry.year,
+            entry.day_number,
+            cells.join(""),
+            if entry.solved.is_ok() {
+                format_time(time)
+            } else {
+                String::new()
+            },
+            100. * time.as_secs_f64() / max_time.as_secs_f64()
+        )
+        .unwrap();
+    }
+    html += "</table>\n";
+    for entry in entries {
+        if let Some(grid) = &entry.grid {
+            writeln!(
+                html,
+                "<details><summary>{} day {}</summary><pre>{}</pre></details>",
+                entry.year,
+                entry.day_number,
+                escape_html(grid)
+            )
+            .unwrap();
+        }
+    }
+    html + "</body></html>\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<Entry> {
+        let answer = |result: &str, micros| Ok((result.to_string(), Duration::from_micros(micros)));
+        vec![
+            Entry {
+                year: 2022,
+                day_number: 25,
+                solved: Ok((
+                    Duration::from_micros(10),
+                    [answer("2=-1=0", 20), Err("panicked: overflow".to_string())],
+                )),
+                expected: [None, None],
+                grid: None,
+            },
+            Entry {
+                year: 2023,
+                day_number: 1,
+                solved: Ok((
+                    Duration::from_micros(100),
+                    [answer("142", 50), answer("281", 50)],
+                )),
+                expected: [Some("142".to_string()), Some("280".to_string())],
+                grid: Some("#.<\n..#\n".to_string()),
+            },
+            Entry {
+                year: 2023,
+                day_number: 2,
+                solved: Err("no input".to_string()),
+                expected: [None, None],
+                grid: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_report() {
+        assert_eq!(
+            format_report(&entries()),
+            "Year  Day  Part 1  Part 2  Time
+2022   25  2=-1=0  failed  30µs
+2023    1  142     281     200µs
+2023    2  no input
+
+2022      0 of 1 days solved in 30µs
+2023      1 of 2 days solved in 200µs
+All years 1 of 3 days solved in 230µs
+
+2022 day 25 part 2: panicked: overflow
+"
+        );
+    }
+
+    #[test]
+    fn test_format_html() {
+        let html = format_html(&entries());
+        assert!(html.contains(
+            "<tr><td>2023</td><td>1</td><td class=\"pass\" title=\"expected 142\">142</td>\
+             <td class=\"fail\" title=\"expected 280\">281</td><td>200µs</td>\
+             <td class=\"timing\"><div class=\"bar\" style=\"width:100.0%\"></div></td></tr>"
+        ));
+        assert!(html.contains("<td class=\"unchecked\">panicked: overflow</td>"));
+        assert!(html.contains("style=\"width:15.0%\""));
+        assert!(html.contains("<summary>2023 day 1</summary><pre>#.&lt;\n..#\n</pre>"));
+        assert_eq!(html.matches("<details>").count(), 1);
+    }
+
+    #[test]
+    fn test_collect() {
+        let root = std::env::temp_dir().join(format!("aoc-report-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("day7")).unwrap();
+        std::fs::write(
+            root.join("day7/input.txt"),
+            "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n",
+        )
+        .unwrap();
+        let entries = collect(&[2023], |_| root.clone(), &Manifest::default());
+        std::fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(entries.len(), 21);
+        for entry in entries.iter() {
+            match (entry.day_number, &entry.solved) {
+                (7, Ok((_, [Ok((part1, _)), Ok((part2, _))]))) => {
+                    assert_eq!((part1.as_str(), part2.as_str()), ("6440", "5905"))
+                }
+                (7, _) => panic!("day 7 should be solved"),
+                (_, solved) => assert_eq!(solved.as_ref().err().unwrap(), "no input"),
+            }
+        }
+    }
+}
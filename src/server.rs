@@ -0,0 +1,358 @@
+use serde_json::{json, Value};
+use std::io::Read;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::Arc;
+use tiny_http::{Header, Method, Request, Response, Server};
+
+use crate::cli::time_function;
+use crate::days;
+use crate::panic_message;
+
+pub struct Config {
+    pub port: u16,
+    pub nb_threads: usize,
+    /// Largest input accepted, in bytes.
+    pub max_input_size: usize,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            port: 8023,
+            nb_threads: 4,
+            max_input_size: 1 << 20,
+        }
+    }
+}
+
+/// Solves the inputs posted to it, listening on the loopback interface only.
+pub struct Service {
+    server: Arc<Server>,
+    nb_threads: usize,
+    max_input_size: usize,
+}
+
+impl Service {
+    pub fn bind(config: Config) -> Result<Service, String> {
+        let server = Server::http(("127.0.0.1", config.port))
+            .map_err(|e| format!("cannot listen on port {}: {}", config.port, e))?;
+        Ok(Service {
+            server: Arc::new(server),
+            nb_threads: config.nb_threads.max(1),
+            max_input_size: config.max_input_size,
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.server
+            .server_addr()
+            .to_ip()
+            .expect("should listen on an ip address")
+            .port()
+    }
+
+    /// Answers requests on `nb_threads` threads, until the server fails.
+    pub fn run(&self) {
+        std::thread::scope(|scope| {
+            for _ in 0..self.nb_threads {
+                scope.spawn(|| {
+                    while let Ok(request) = self.server.recv() {
+                        self.handle(request);
+                    }
+                });
+            }
+        });
+    }
+
+    fn handle(&self, mut request: Request) {
+        let url = request.url().to_string();
+        let (path, query) = url.split_once('?').unwrap_or((&url, ""));
+        let segments = path.split('/').skip(1).collect::<Vec<_>>();
+        let (status, body) = match (request.method(), segments.as_slice()) {
+            (Method::Get, ["years"]) => (200, json!({ "years": days::years() })),
+            (Method::Get, ["days"]) => list_days(&days::latest_year().to_string()),
+            (Method::Get, ["days", year]) => list_days(year),
+            (Method::Post, ["solve", day, part]) => {
+                let year = days::latest_year().to_string();
+                self.handle_solve(&mut request, [&year, day, part], query)
+            }
+            (Method::Post, ["solve", year, day, part]) => {
+                self.handle_solve(&mut request, [year, day, part], query)
+            }
+            (_, ["years"] | ["days"] | ["days", _] | ["solve", _, _] | ["solve", _, _, _]) => {
+                error(405, "method not allowed")
+            }
+            _ => error(404, "not found"),
+        };
+        let header =
+            Header::from_bytes("Content-Type", "application/json").expect("header should be valid");
+        let response = Response::from_string(body.to_string())
+            .with_status_code(status)
+            .with_header(header);
+        // the client may be gone already, nothing to do about it
+        let _ = request.respond(response);
+    }
+
+    /// Checks the route and the parameters before reading the input, so that a wrong day or part
+    /// is reported as such whatever the input.
+    fn handle_solve(&self, request: &mut Request, route: [&str; 3], query: &str) -> (u16, Value) {
+        let solved = check_route(route).and_then(|(year, day_number, part)| {
+            let params = parse_query(query)?;
+            let input = self.read_body(request)?;
+            Ok(solve(year, day_number, part, &params, &input))
+        });
+        solved.unwrap_or_else(|error| error)
+    }
+
+    fn read_body(&self, request: &mut Request) -> Result<String, (u16, Value)> {
+        let too_large = || {
+            error(
+                413,
+                &format!("input larger than {} bytes", self.max_input_size),
+            )
+        };
+        if request
+            .body_length()
+            .is_some_and(|length| length > self.max_input_size)
+        {
+            return Err(too_large());
+        }
+        let mut body = vec![];
+        request
+            .as_reader()
+            .take(self.max_input_size as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|_| error(400, "cannot read input"))?;
+        if body.len() > self.max_input_size {
+            return Err(too_large());
+        }
+        String::from_utf8(body).map_err(|_| error(400, "input should be utf-8 text"))
+    }
+}
+
+fn error(status: u16, message: &str) -> (u16, Value) {
+    (status, json!({ "error": message }))
+}
+
+fn list_days(year: &str) -> (u16, Value) {
+    match year.parse::<u32>() {
+        Ok(year) if days::years().contains(&year) => (
+            200,
+            json!({ "year": year, "days": days::day_numbers(year) }),
+        ),
+        _ => error(404, &format!("no year {}", year)),
+    }
+}
+
+fn check_route([year, day_number, part]: [&str; 3]) -> Result<(u32, u32, u8), (u16, Value)> {
+    let (Ok(year), Ok(day_number)) = (year.parse::<u32>(), day_number.parse::<u32>()) else {
+        return Err(error(404, "year and day should be numbers"));
+    };
+    if !days::day_numbers(year).contains(&day_number) {
+        return Err(error(404, &format!("no day {} in {}", day_number, year)));
+    }
+    match part {
+        "1" => Ok((year, day_number, 1)),
+        "2" => Ok((year, day_number, 2)),
+        _ => Err(error(404, "part should be 1 or 2")),
+    }
+}
+
+/// The `name=value` parameters of a query, decoded.
+fn parse_query(query: &str) -> Result<Vec<(String, String)>, (u16, Value)> {
+    query
+        .split('&')
+        .filter_map(|param| param.split_once('='))
+        .map(|(name, value)| match (decode(name), decode(value)) {
+            (Some(name), Some(value)) => Ok((name, value)),
+            _ => Err(error(
+                400,
+                &format!("malformed parameter {}={}", name, value),
+            )),
+        })
+        .collect()
+}
+
+/// Decodes a component of a query, `+` standing for a space and `%XX` for a byte.
+fn decode(component: &str) -> Option<String> {
+    let mut bytes = vec![];
+    let mut rest = component.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        rest = tail;
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = rest
+                    .get(..2)
+                    .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))?;
+                bytes.push(u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()?);
+                rest = &rest[2..];
+            }
+            _ => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn solve(
+    year: u32,
+    day_number: u32,
+    part: u8,
+    params: &[(String, String)],
+    input: &str,
+) -> (u16, Value) {
+    let problems = days::lint(year, day_number, input).expect("day should exist");
+    if !problems.is_empty() {
+        let problems = problems
+            .iter()
+            .map(|problem| problem.to_string())
+            .collect::<Vec<_>>();
+        return (
+            422,
+            json!({ "error": "invalid input", "problems": problems }),
+        );
+    }
+
+    let solved = catch_unwind(AssertUnwindSafe(|| {
+        let (day, parse_time) =
+            time_function(|| days::make_day(year, day_number, input.as_bytes()));
+        let mut day = day.expect("day should exist");
+        for (name, value) in params {
+            day.set_param(name, value)?;
+        }
+        let (answer, solve_time) = time_function(|| day.try_solution(part));
+        Ok::<_, String>((answer, parse_time, solve_time))
+    }));
+    match solved {
+        Ok(Ok((Ok(answer), parse_time, solve_time))) => (
+            200,
+            json!({
+                "year": year,
+                "day": day_number,
+                "part": part,
+                "answer": answer,
+                "parse_time_us": parse_time.as_micros() as u64,
+                "solve_time_us": solve_time.as_micros() as u64,
+            }),
+        ),
+        Ok(Ok((Err(reason), ..))) => error(422, &format!("solving the input failed: {}", reason)),
+        Ok(Err(message)) => error(400, &message),
+        Err(payload) => error(
+            422,
+            &format!("solving the input failed: {}", panic_message(payload)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::net::TcpStream;
+
+    fn start(max_input_size: usize) -> u16 {
+        let service = Service::bind(Config {
+            port: 0,
+            nb_threads: 2,
+            max_input_size,
+        })
+        .unwrap();
+        let port = service.port();
+        std::thread::spawn(move || service.run());
+        port
+    }
+
+    fn send(port: u16, method: &str, path: &str, body: &str) -> (u16, Value) {
+        let mut stream = TcpStream::connect(("127.0.0.1", port)).unwrap();
+        write!(
+            stream,
+            "{} {} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            body.len(),
+            body
+        )
+        .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        let (head, body) = response.split_once("\r\n\r\n").unwrap();
+        let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+        (status, serde_json::from_str(body).unwrap())
+    }
+
+    #[test]
+    fn test_solve() {
+        let port = start(1 << 10);
+        let (status, body) = send(
+            port,
+            "POST",
+            "/solve/9/1",
+            "0 3 6 9 12 15\n1 3 6 10 15 21\n",
+        );
+        assert_eq!(status, 200);
+        assert_eq!(body["year"], 2023);
+        assert_eq!(body["day"], 9);
+        assert_eq!(body["part"], 1);
+        assert_eq!(body["answer"], "46");
+        assert!(body["solve_time_us"].is_u64());
+        let (status, body) = send(port, "POST", "/solve/9/1", "9223372036854775808 0\n");
+        assert_eq!(status, 422);
+        assert_eq!(
+            body["error"],
+            "solving the input failed: overflow of a 64-bit integer, run with --bigint for an \
+             exact answer"
+        );
+        let (_, body) = send(
+            port,
+            "POST",
+            "/solve/9/1?bigint=true",
+            "9223372036854775808 0\n",
+        );
+        assert_eq!(body["answer"], "-9223372036854775808");
+
+        let garden = "...\n.S.\n...\n";
+        let (_, body) = send(port, "POST", "/solve/2023/21/1?nb_steps=1", garden);
+        assert_eq!(body["answer"], "4");
+        let (_, body) = send(port, "POST", "/solve/21/1?nb%5Fsteps=%2B1", garden);
+        assert_eq!(body["answer"], "4");
+        let (status, _) = send(port, "POST", "/solve/21/1?speed=1", garden);
+        assert_eq!(status, 400);
+        let (status, body) = send(port, "POST", "/solve/21/1?nb_steps=%G1", garden);
+        assert_eq!(status, 400);
+        assert_eq!(body["error"], "malformed parameter nb_steps=%G1");
+    }
+
+    #[test]
+    fn test_list_days() {
+        let port = start(1 << 10);
+        let (status, body) = send(port, "GET", "/days", "");
+        assert_eq!(status, 200);
+        assert_eq!(body["days"][0], 1);
+        assert_eq!(body["days"].as_array().unwrap().len(), 21);
+        assert_eq!(send(port, "GET", "/days/2023", "").1, body);
+        assert_eq!(send(port, "GET", "/years", "").1["years"][0], 2023);
+        assert_eq!(send(port, "GET", "/days/1999", "").0, 404);
+    }
+
+    #[test]
+    fn test_errors() {
+        let port = start(20);
+        assert_eq!(send(port, "GET", "/solve/1/1", "").0, 405);
+        assert_eq!(send(port, "POST", "/solve/99/1", "1\n").0, 404);
+        assert_eq!(send(port, "POST", "/solve/9/3", "1\n").0, 404);
+        // the route is checked before the input
+        let (status, body) = send(port, "POST", "/solve/7/3", "32T3K 765\nT55J5A 6\n");
+        assert_eq!(status, 404);
+        assert_eq!(body["error"], "part should be 1 or 2");
+        assert_eq!(send(port, "GET", "/answers", "").0, 404);
+        let (status, _) = send(port, "POST", "/solve/9/1", &"1 ".repeat(12));
+        assert_eq!(status, 413);
+        let (status, body) = send(port, "POST", "/solve/7/1", "32T3K 765\nT55J5A 6\n");
+        assert_eq!(status, 422);
+        assert_eq!(body["problems"][0], "line 2: expected hand");
+        // a pattern of day 13 without any reflection passes lint but has no answer
+        let (status, body) = send(port, "POST", "/solve/13/1", "#..\n..#\n");
+        assert_eq!(status, 422);
+        assert_eq!(body["error"], "solving the input failed: no sym");
+    }
+}
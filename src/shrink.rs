@@ -0,0 +1,279 @@
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+/// What makes an input interesting enough to be kept while shrinking it.
+pub enum Predicate {
+    /// The day panics, at the same place as on the original input.
+    Panic,
+    /// The answer changes when these parameters are added.
+    Variant(Vec<(String, String)>),
+    /// The answer differs from the one printed by this shell command, given the input on stdin.
+    Reference(String),
+}
+
+enum Outcome {
+    Answer(String),
+    Panic(String),
+    Failure,
+}
+
+/// Solves inputs in a child process running `aoc answer`, so that panics and endless loops on
+/// a shrunk input do not take the shrinker down.
+pub struct Runner {
+    pub program: PathBuf,
+    pub year: u32,
+    pub day_number: u32,
+    pub part: u8,
+    pub params: Vec<(String, String)>,
+    pub timeout: Duration,
+}
+
+impl Runner {
+    fn solve(&self, input: &str, extra_params: &[(String, String)]) -> Outcome {
+        let mut command = Command::new(&self.program);
+        command
+            .arg("answer")
+            .arg(self.day_number.to_string())
+            .arg(self.part.to_string())
+            .args(["--year", &self.year.to_string()])
+            .args(
+                self.params
+                    .iter()
+                    .chain(extra_params)
+                    .map(|(name, value)| format!("{}={}", name, value)),
+            );
+        run_command(command, input, self.timeout)
+    }
+}
+
+fn run_command(mut command: Command, input: &str, timeout: Duration) -> Outcome {
+    let Ok(mut child) = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+    else {
+        return Outcome::Failure;
+    };
+    let mut stdin = child.stdin.take().unwrap();
+    let input = input.to_string();
+    // the child may exit before reading everything, a broken pipe is not an error here
+    std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+
+    let start = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => break,
+            Ok(None) if start.elapsed() < timeout => std::thread::sleep(Duration::from_millis(1)),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Outcome::Failure;
+            }
+        }
+    }
+
+    let Ok(output) = child.wait_with_output() else {
+        return Outcome::Failure;
+    };
+    if output.status.success() {
+        Outcome::Answer(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        match panic_location(&String::from_utf8_lossy(&output.stderr)) {
+            Some(location) => Outcome::Panic(location.to_string()),
+            None => Outcome::Failure,
+        }
+    }
+}
+
+fn panic_location(stderr: &str) -> Option<&str> {
+    let (_, location) = stderr
+        .lines()
+        .find_map(|line| line.split_once("panicked at "))?;
+    Some(location.trim_end_matches(':'))
+}
+
+impl Predicate {
+    /// Returns a signature of the failure when the predicate holds, such as the panic location,
+    /// so that shrinking does not drift towards another failure.
+    fn check(&self, runner: &Runner, input: &str) -> Option<String> {
+        match self {
+            Predicate::Panic => match runner.solve(input, &[]) {
+                Outcome::Panic(location) => Some(location),
+                Outcome::Answer(_) | Outcome::Failure => None,
+            },
+            Predicate::Variant(params) => {
+                match (runner.solve(input, &[]), runner.solve(input, params)) {
+                    (Outcome::Answer(answer), Outcome::Answer(variant)) if answer != variant => {
+                        Some(String::new())
+                    }
+                    _ => None,
+                }
+            }
+            Predicate::Reference(reference) => {
+                let Outcome::Answer(answer) = runner.solve(input, &[]) else {
+                    return None;
+                };
+                let mut command = Command::new("sh");
+                command.arg("-c").arg(reference);
+                match run_command(command, input, runner.timeout) {
+                    Outcome::Answer(expected) if expected != answer => Some(String::new()),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+/// Shrinks an input on which the predicate holds to a minimal one on which it still does.
+pub fn shrink_failing_input(
+    runner: &Runner,
+    predicate: &Predicate,
+    input: &str,
+) -> Result<String, String> {
+    let signature = predicate
+        .check(runner, input)
+        .ok_or("the predicate does not hold on the input")?;
+    Ok(shrink(input, |candidate| {
+        predicate.check(runner, candidate).as_ref() == Some(&signature)
+    }))
+}
+
+/// Removes blocks, lines and grid columns from `input` for as long as `holds` stays true on what
+/// is left, until nothing more can be removed.
+pub fn shrink(input: &str, mut holds: impl FnMut(&str) -> bool) -> String {
+    let mut current = join_lines(&split_lines(input));
+    loop {
+        let previous_length = current.len();
+        let blocks = ddmin(split_blocks(&current), |blocks| holds(&join_blocks(blocks)));
+        current = join_blocks(&blocks);
+        let lines = ddmin(split_lines(&current), |lines| holds(&join_lines(lines)));
+        current = join_lines(&lines);
+        for block_index in 0..split_blocks(&current).len() {
+            current = shrink_grid_columns(&current, block_index, &mut holds);
+        }
+        if current.len() == previous_length {
+            return current;
+        }
+    }
+}
+
+/// Delta debugging: removes ever smaller chunks of `items` while `holds` stays true.
+fn ddmin<T: Clone>(mut items: Vec<T>, mut holds: impl FnMut(&[T]) -> bool) -> Vec<T> {
+    let mut nb_chunks = 2;
+    while items.len() >= 2 {
+        let chunk_size = items.len().div_ceil(nb_chunks);
+        let reduced = (0..items.len()).step_by(chunk_size).find_map(|start| {
+            let complement = items[..start]
+                .iter()
+                .chain(items.iter().skip(start + chunk_size))
+                .cloned()
+                .collect::<Vec<_>>();
+            holds(&complement).then_some(complement)
+        });
+        match reduced {
+            Some(complement) => {
+                items = complement;
+                nb_chunks = (nb_chunks - 1).max(2);
+            }
+            None if nb_chunks >= items.len() => break,
+            None => nb_chunks = (nb_chunks * 2).min(items.len()),
+        }
+    }
+    items
+}
+
+fn shrink_grid_columns(
+    input: &str,
+    block_index: usize,
+    holds: &mut impl FnMut(&str) -> bool,
+) -> String {
+    let blocks = split_blocks(input);
+    let rows = blocks[block_index]
+        .lines()
+        .map(|line| line.chars().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let width = rows.first().map_or(0, Vec::len);
+    let is_grid = width >= 2
+        && rows
+            .iter()
+            .all(|row| row.len() == width && !row.iter().any(|c| c.is_whitespace()));
+    if !is_grid {
+        return input.to_string();
+    }
+
+    let with_columns = |columns: &[usize]| {
+        let mut blocks = blocks.clone();
+        blocks[block_index] = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| row[*column])
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        join_blocks(&blocks)
+    };
+    let columns = ddmin((0..width).collect(), |columns| {
+        holds(&with_columns(columns))
+    });
+    with_columns(&columns)
+}
+
+fn split_lines(input: &str) -> Vec<String> {
+    input.lines().map(String::from).collect()
+}
+
+fn join_lines(lines: &[String]) -> String {
+    lines.iter().map(|line| format!("{}\n", line)).collect()
+}
+
+fn split_blocks(input: &str) -> Vec<String> {
+    input
+        .trim_end_matches('\n')
+        .split("\n\n")
+        .map(String::from)
+        .collect()
+}
+
+fn join_blocks(blocks: &[String]) -> String {
+    join_lines(&[blocks.join("\n\n")])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrink_lines() {
+        let input = (0..100).map(|i| format!("{}\n", i)).collect::<String>();
+        let shrunk = shrink(&input, |candidate| {
+            let lines = candidate.lines().collect::<Vec<_>>();
+            lines.contains(&"17") && lines.contains(&"64")
+        });
+        assert_eq!(shrunk, "17\n64\n");
+    }
+
+    #[test]
+    fn test_shrink_blocks_and_grid() {
+        let input = "ab\ncd\n\n.....\n..#..\n.....\n\n#.#\n.#.\n";
+        let shrunk = shrink(input, |candidate| candidate.contains('#'));
+        assert_eq!(shrunk, "#\n");
+
+        // keeps the four rocks in the middle of a grid
+        let input = "......\n..##..\n..##..\n......\n";
+        let shrunk = shrink(input, |candidate| candidate.matches('#').count() == 4);
+        assert_eq!(shrunk, "##\n##\n");
+    }
+
+    #[test]
+    fn test_panic_location() {
+        let stderr = "\nthread 'main' (42) panicked at src/days/day8.rs:41:45:\nno entry found for key\nnote: run with `RUST_BACKTRACE=1`";
+        assert_eq!(panic_location(stderr), Some("src/days/day8.rs:41:45"));
+        assert_eq!(panic_location("error: file not found"), None);
+    }
+}
@@ -0,0 +1,66 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::cli::time_function;
+use crate::{days, encryption, panic_message};
+
+/// The answer of a part with the time it took, or why there is none.
+pub type Answer = Result<(String, Duration), String>;
+
+/// Reads, parses and solves an input, returning the parse time and the answers, panics included
+/// as errors.
+pub fn solve(
+    year: u32,
+    day_number: u32,
+    input_path: &Path,
+    params: &[(&str, &str)],
+) -> Result<(Duration, [Answer; 2]), String> {
+    let input = encryption::read_input_file(input_path)?;
+    let made = catch_unwind(|| {
+        time_function(|| {
+            let mut day = days::make_day(year, day_number, input.as_bytes())
+                .ok_or_else(|| format!("no day {} in {}", day_number, year))?;
+            for (name, value) in params {
+                day.set_param(name, value)?;
+            }
+            Ok::<_, String>(day)
+        })
+    });
+    let (day, parse_time) = match made {
+        Ok((day, parse_time)) => (day?, parse_time),
+        Err(payload) => return Err(format!("parsing panicked: {}", panic_message(payload))),
+    };
+    let answers = [1, 2].map(|part| {
+        let solved = catch_unwind(AssertUnwindSafe(|| {
+            time_function(|| day.try_solution(part))
+        }));
+        match solved {
+            Ok((answer, duration)) => answer.map(|answer| (answer, duration)),
+            Err(payload) => Err(format!("panicked: {}", panic_message(payload))),
+        }
+    });
+    Ok((parse_time, answers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let directory = std::env::temp_dir().join(format!("aoc-solver-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let input_path = directory.join("input.txt");
+        std::fs::write(
+            &input_path,
+            "32T3K 765\nT55J5 684\nKK677 28\nKTJJT 220\nQQQJA 483\n",
+        )
+        .unwrap();
+        let (_, [part1, part2]) = solve(2023, 7, &input_path, &[]).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+        assert_eq!(part1.unwrap().0, "6440");
+        assert_eq!(part2.unwrap().0, "5905");
+        assert!(solve(2023, 7, &input_path, &[]).is_err());
+    }
+}
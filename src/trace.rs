@@ -0,0 +1,157 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::io::Write;
+use std::sync::OnceLock;
+
+/// How `--explain` prints the events emitted by the days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Indented,
+    JsonLines,
+}
+
+pub type Field<'a> = (&'a str, &'a dyn Display);
+
+static FORMAT: OnceLock<Format> = OnceLock::new();
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    static CAPTURED: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+pub fn enable(format: Format) {
+    FORMAT.set(format).expect("tracing is enabled only once");
+}
+
+pub fn is_enabled() -> bool {
+    FORMAT.get().is_some() || CAPTURED.with_borrow(Option::is_some)
+}
+
+/// Runs `f`, collecting as indented lines the events it emits on this thread instead of printing
+/// them.
+pub fn capture<T>(f: impl FnOnce() -> T) -> (T, Vec<String>) {
+    CAPTURED.set(Some(vec![]));
+    let result = f();
+    (result, CAPTURED.take().unwrap_or_default())
+}
+
+/// Emits an event made of a name and some fields, if tracing is enabled.
+pub fn event(name: &str, fields: &[Field]) {
+    let captured = CAPTURED.with_borrow_mut(|captured| match captured {
+        Some(lines) => {
+            lines.push(format_event(Format::Indented, DEPTH.get(), name, fields));
+            true
+        }
+        None => false,
+    });
+    if captured {
+        return;
+    }
+    if let Some(format) = FORMAT.get() {
+        let line = format_event(*format, DEPTH.get(), name, fields);
+        writeln!(std::io::stdout().lock(), "{}", line).expect("could write trace");
+    }
+}
+
+/// Emits an event under which the events emitted until the span is dropped are nested.
+pub fn span(name: &str, fields: &[Field]) -> Span {
+    event(name, fields);
+    DEPTH.set(DEPTH.get() + 1);
+    Span
+}
+
+pub struct Span;
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        DEPTH.set(DEPTH.get() - 1);
+    }
+}
+
+/// Emits an event from `name` and `field = value` pairs, without evaluating the values when
+/// tracing is disabled.
+macro_rules! trace_event {
+    ($name:expr $(, $field:ident = $value:expr)* $(,)?) => {
+        if $crate::trace::is_enabled() {
+            $crate::trace::event(
+                $name,
+                &[$((stringify!($field), &$value as &dyn std::fmt::Display)),*],
+            );
+        }
+    };
+}
+pub(crate) use trace_event;
+
+fn format_event(format: Format, depth: usize, name: &str, fields: &[Field]) -> String {
+    match format {
+        Format::Indented => {
+            let mut line = "  ".repeat(depth) + name;
+            for (field, value) in fields {
+                let value = value.to_string();
+                if value.contains(char::is_whitespace) {
+                    line += &format!(" {}={:?}", field, value);
+                } else {
+                    line += &format!(" {}={}", field, value);
+                }
+            }
+            line
+        }
+        Format::JsonLines => {
+            let mut line = format!("{{\"depth\":{},\"event\":{}", depth, to_json(name));
+            for (field, value) in fields {
+                let value = value.to_string();
+                // numbers the way JSON writes them, so neither "+5" nor "007"
+                if value.parse::<serde_json::Number>().is_ok() {
+                    line += &format!(",{}:{}", to_json(field), value);
+                } else {
+                    line += &format!(",{}:{}", to_json(field), to_json(&value));
+                }
+            }
+            line + "}"
+        }
+    }
+}
+
+fn to_json(s: &str) -> String {
+    serde_json::Value::from(s).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_event() {
+        let fields: [Field; 3] = [("index", &3), ("axis", &"row"), ("path", &"in -> px")];
+        assert_eq!(
+            format_event(Format::Indented, 2, "island", &fields),
+            "    island index=3 axis=row path=\"in -> px\""
+        );
+        assert_eq!(
+            format_event(Format::JsonLines, 2, "island", &fields),
+            r#"{"depth":2,"event":"island","index":3,"axis":"row","path":"in -> px"}"#
+        );
+        let fields: [Field; 4] = [("a", &"+5"), ("b", &"007"), ("c", &-1.5), ("d", &"1e3")];
+        assert_eq!(
+            format_event(Format::JsonLines, 0, "values", &fields),
+            r#"{"depth":0,"event":"values","a":"+5","b":"007","c":-1.5,"d":1e3}"#
+        );
+    }
+
+    #[test]
+    fn test_capture() {
+        let (answer, lines) = capture(|| {
+            let _part = span("part", &[("number", &1)]);
+            trace_event!("line", number = 3, nb_ways = 10);
+            42
+        });
+        assert_eq!(answer, 42);
+        assert_eq!(lines, ["part number=1", "  line number=3 nb_ways=10"]);
+        assert!(!is_enabled());
+    }
+
+    #[test]
+    fn test_to_json() {
+        assert_eq!(to_json("a \"b\"\\\n\t\u{1}"), r#""a \"b\"\\\n\t\u0001""#);
+    }
+}
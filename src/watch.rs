@@ -0,0 +1,181 @@
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+use crate::cli::choose_unit;
+use crate::encryption;
+use crate::solver::{self, Answer};
+
+/// How long to wait for the other events of a single save before running again.
+const SETTLE_TIME: Duration = Duration::from_millis(100);
+
+/// Solves the input each time it changes on disk, until the watcher fails. The directory of the
+/// inputs of the year, when the input is one of them, is watched recursively so that the input is
+/// noticed even when it or its day directory only comes later or is renamed into place.
+pub fn watch(
+    year: u32,
+    day_number: u32,
+    input_path: &Path,
+    input_root: Option<&Path>,
+    params: &[(&str, &str)],
+) -> Result<(), String> {
+    let (directory, mode) = match (input_root, input_path.parent()) {
+        (Some(input_root), _) => (input_root, RecursiveMode::Recursive),
+        (None, Some(directory)) if directory != Path::new("") => {
+            (directory, RecursiveMode::NonRecursive)
+        }
+        (None, _) => (Path::new("."), RecursiveMode::NonRecursive),
+    };
+    std::fs::create_dir_all(directory)
+        .map_err(|e| format!("cannot create {}: {}", directory.display(), e))?;
+    let (sender, receiver) = channel();
+    let mut watcher =
+        notify::recommended_watcher(sender).map_err(|e| format!("cannot watch files: {}", e))?;
+    watcher
+        .watch(&absolute(directory), mode)
+        .map_err(|e| format!("cannot watch {}: {}", directory.display(), e))?;
+
+    let mut previous = None;
+    loop {
+        println!("Solving {}", input_path.display());
+        let answers = run(year, day_number, input_path, params, previous.as_ref());
+        previous = Some(answers);
+        println!("\nWaiting for {} to change\n", input_path.display());
+        wait_for_change(&receiver, input_path)?;
+    }
+}
+
+fn run(
+    year: u32,
+    day_number: u32,
+    input_path: &Path,
+    params: &[(&str, &str)],
+    previous: Option<&[Answer; 2]>,
+) -> [Answer; 2] {
+    let answers = match solver::solve(year, day_number, input_path, params) {
+        Ok((parse_time, answers)) => {
+            let (time, unit) = choose_unit(parse_time);
+            println!("Parse time : {}{}", time, unit);
+            answers
+        }
+        Err(error) => [Err(error.clone()), Err(error)],
+    };
+    for (part, answer) in answers.iter().enumerate() {
+        let previous = previous.map(|previous| &previous[part]);
+        match answer {
+            Ok((result, duration)) => {
+                let (time, unit) = choose_unit(*duration);
+                println!(
+                    "Solution {} : {} ({}{}){}",
+                    part + 1,
+                    result,
+                    time,
+                    unit,
+                    describe_change(previous, answer)
+                );
+            }
+            Err(error) => println!("Solution {} : failed, {}", part + 1, error),
+        }
+    }
+    answers
+}
+
+/// Compares an answer with the one of the previous run, timings left out.
+fn describe_change(previous: Option<&Answer>, answer: &Answer) -> String {
+    match (previous, answer) {
+        (None, _) | (_, Err(_)) => String::new(),
+        (Some(Err(_)), Ok(_)) => ", was failing".to_string(),
+        (Some(Ok((previous, _))), Ok((result, _))) if previous == result => {
+            ", unchanged".to_string()
+        }
+        (Some(Ok((previous, _))), Ok(_)) => format!(", was {}", previous),
+    }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+    std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Whether the event is about the input, its encrypted version, or a directory holding it, whose
+/// files may come before it is watched.
+fn changes_input(event: &Event, input_path: &Path) -> bool {
+    let encrypted_path = encryption::encrypted_path(input_path);
+    let is_input = event
+        .paths
+        .iter()
+        .any(|path| *path == encrypted_path || input_path.starts_with(path));
+    is_input && !matches!(event.kind, EventKind::Access(_))
+}
+
+/// Blocks until the input, or its encrypted version, is written, created, renamed or removed.
+fn wait_for_change(
+    receiver: &Receiver<notify::Result<Event>>,
+    input_path: &Path,
+) -> Result<(), String> {
+    let input_path = absolute(input_path);
+    loop {
+        let event = receiver
+            .recv()
+            .map_err(|_| "file watcher stopped".to_string())?
+            .map_err(|e| format!("file watcher failed: {}", e))?;
+        if changes_input(&event, &input_path) {
+            break;
+        }
+    }
+    // editors often save a file in several steps
+    while receiver.recv_timeout(SETTLE_TIME).is_ok() {}
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_change() {
+        let answer = |result: &str, micros| Ok((result.to_string(), Duration::from_micros(micros)));
+        assert_eq!(describe_change(None, &answer("6440", 12)), "");
+        assert_eq!(
+            describe_change(Some(&answer("6440", 15)), &answer("6440", 12)),
+            ", unchanged"
+        );
+        assert_eq!(
+            describe_change(Some(&answer("6592", 15)), &answer("6440", 12)),
+            ", was 6592"
+        );
+        assert_eq!(
+            describe_change(Some(&Err("panicked".to_string())), &answer("6440", 12)),
+            ", was failing"
+        );
+    }
+
+    #[test]
+    fn test_wait_for_change() {
+        let directory = std::env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let input_path = directory.join("day7/input.txt");
+        std::fs::create_dir_all(directory.join("day7")).unwrap();
+
+        let (sender, receiver) = channel();
+        let mut watcher = notify::recommended_watcher(sender).unwrap();
+        watcher.watch(&directory, RecursiveMode::Recursive).unwrap();
+        // other files around the input are not changes of it
+        std::fs::write(directory.join("day7/notes.md"), "").unwrap();
+        std::fs::write(directory.join("input.txt"), "").unwrap();
+        std::fs::write(directory.join("day7/input.txt.tmp"), "32T3K 765\n").unwrap();
+        while let Ok(event) = receiver.recv_timeout(Duration::from_millis(200)) {
+            assert!(!changes_input(&event.unwrap(), &input_path));
+        }
+        // nor is a day directory coming later, which is watched anyway
+        std::fs::create_dir_all(directory.join("day8")).unwrap();
+        std::fs::write(directory.join("day8/input.txt"), "").unwrap();
+        while let Ok(event) = receiver.recv_timeout(Duration::from_millis(200)) {
+            assert!(!changes_input(&event.unwrap(), &input_path));
+        }
+
+        std::fs::rename(directory.join("day7/input.txt.tmp"), &input_path).unwrap();
+        wait_for_change(&receiver, &input_path).unwrap();
+        std::fs::remove_dir_all(&directory).unwrap();
+    }
+}